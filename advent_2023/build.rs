@@ -0,0 +1,94 @@
+//! Generates a test for each part answer listed in a sample input's sidecar answers file
+//!
+//! Sample inputs are `input/<YEAR>/NN-t*.txt`, with expected answers in the matching
+//! `NN-t*.answers.toml`. The tests are included by `tests/samples.rs`. A sample name may only
+//! hold letters, digits, `-` and `_`, since it's part of its test names.
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    let input_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("input");
+    println!("cargo:rerun-if-changed={}", input_dir.display());
+
+    let mut tests = String::new();
+    for (year, year_dir) in subdirs(&input_dir) {
+        println!("cargo:rerun-if-changed={}", year_dir.display());
+
+        let mut samples = samples(&year_dir);
+        samples.sort();
+        for (day, sample, parts) in samples {
+            assert!(
+                sample
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+                "sample input {}/{}.txt needs a name of letters, digits, '-' and '_' to name its tests",
+                year_dir.display(),
+                sample
+            );
+            for part in parts {
+                let name = format!("sample_{}_{}_part{}", year, sample, part).replace('-', "_");
+                writeln!(
+                    tests,
+                    "#[test]\nfn {}() {{\n    check_sample({}, {}, {}, {:?});\n}}\n",
+                    name, year, day, part, sample
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("sample_tests.rs");
+    fs::write(out_path, tests).unwrap();
+}
+
+/// every event year directory under `dir`
+fn subdirs(dir: &Path) -> Vec<(usize, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let year = entry.file_name().to_str()?.parse().ok()?;
+            Some((year, entry.path()))
+        })
+        .collect()
+}
+
+/// the day, file stem and expected answer parts of every sample input in `dir` with a sidecar
+/// answers file
+fn samples(dir: &Path) -> Vec<(usize, String, Vec<usize>)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            let stem = file_name.strip_suffix(".txt")?;
+            let (day, suffix) = stem.split_once('-')?;
+            if !suffix.starts_with('t') {
+                return None;
+            }
+            let day = day.parse().ok()?;
+
+            let sidecar = fs::read_to_string(dir.join(format!("{}.answers.toml", stem))).ok()?;
+            let parts = sidecar
+                .lines()
+                .filter_map(|line| {
+                    let (key, _) = line.split_once('=')?;
+                    key.trim().strip_prefix("part")?.parse().ok()
+                })
+                .collect();
+
+            Some((day, stem.to_string(), parts))
+        })
+        .collect()
+}
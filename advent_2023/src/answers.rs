@@ -0,0 +1,203 @@
+//! Expected puzzle answers, for verifying solutions against real inputs
+//!
+//! Each event year keeps its answers in its own file, stored in a small subset of TOML with one
+//! table per day:
+//!
+//! ```toml
+//! [day01]
+//! part1 = "54632"
+//! part2 = 54019
+//! ```
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::{read_file, report::Record, Error};
+
+/// Expected answers keyed by day and part
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Answers(HashMap<(usize, usize), String>);
+
+impl Answers {
+    /// read an answers file
+    pub fn load(file_path: &str) -> Result<Self, Error> {
+        read_file(file_path)?.parse()
+    }
+
+    /// the expected answer for a day and part, if known
+    pub fn get(&self, day: usize, part: usize) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    /// compare a solver result to its expected answer
+    pub fn verify(&self, record: &Record) -> Verdict {
+        match (&record.result, self.get(record.day, record.part)) {
+            (Err(err), _) => Verdict::Error(err.to_string()),
+            (Ok(actual), None) => Verdict::Missing {
+                actual: actual.clone(),
+            },
+            (Ok(actual), Some(expected)) if actual == expected => Verdict::Pass,
+            (Ok(actual), Some(expected)) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: actual.clone(),
+            },
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = HashMap::new();
+        let mut day = None;
+
+        for (idx, line) in s.lines().enumerate() {
+            let line_err = |reason: String| Error::at_line(idx + 1, reason);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(table) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let number = table
+                    .trim()
+                    .strip_prefix("day")
+                    .and_then(|number| number.parse().ok())
+                    .ok_or_else(|| line_err(format!("Invalid day table '{}'", line)))?;
+                day = Some(number);
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| line_err(format!("Expected 'partN = answer', found '{}'", line)))?;
+            let part = key
+                .trim()
+                .strip_prefix("part")
+                .and_then(|number| number.parse().ok())
+                .ok_or_else(|| line_err(format!("Invalid part key '{}'", key.trim())))?;
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            let day = day.ok_or_else(|| line_err("Answer outside of a day table".to_string()))?;
+
+            answers.insert((day, part), value.to_string());
+        }
+
+        Ok(Self(answers))
+    }
+}
+
+/// Outcome of checking a solver result against its expected answer
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    /// Result matches the expected answer
+    Pass,
+    /// Result differs from the expected answer
+    Fail {
+        /// Expected answer
+        expected: String,
+        /// Solver result
+        actual: String,
+    },
+    /// No expected answer is recorded
+    Missing {
+        /// Solver result
+        actual: String,
+    },
+    /// Solver failed to produce a result
+    Error(String),
+}
+
+impl Verdict {
+    /// whether this verdict should fail a verification run
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Fail { .. } | Verdict::Error(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Verdict::Missing { actual } => write!(f, "MISSING (got {})", actual),
+            Verdict::Error(err) => write!(f, "FAIL ({})", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+
+    const ANSWERS: &str = r#"
+# expected answers
+[day01]
+part1 = "142"
+part2 = 281
+
+[day04]
+part1 = "13"
+"#;
+
+    fn record(day: usize, part: usize, result: Result<String, Error>) -> Record {
+        Record {
+            year: 2023,
+            day,
+            part,
+            label: "label",
+            result,
+            duration: Duration::default(),
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn parse_answers() {
+        let msg = "should parse expected answers keyed by day and part";
+        let answers: Answers = ANSWERS.parse().unwrap();
+        assert_eq!(answers.get(1, 1), Some("142"), "{}", msg);
+        assert_eq!(answers.get(1, 2), Some("281"), "{}", msg);
+        assert_eq!(answers.get(4, 1), Some("13"), "{}", msg);
+        assert_eq!(answers.get(4, 2), None, "{}", msg);
+
+        let msg = "should report the line of an invalid entry";
+        let expected = Some(2);
+        let actual = "[day01]\nanswer = 3".parse::<Answers>().unwrap_err().line;
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn verify() {
+        let msg = "should compare solver results to the expected answers";
+        let answers: Answers = ANSWERS.parse().unwrap();
+
+        let expected = Verdict::Pass;
+        let actual = answers.verify(&record(1, 1, Ok("142".to_string())));
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Verdict::Fail {
+            expected: "281".to_string(),
+            actual: "280".to_string(),
+        };
+        let actual = answers.verify(&record(1, 2, Ok("280".to_string())));
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Verdict::Missing {
+            actual: "30".to_string(),
+        };
+        let actual = answers.verify(&record(4, 2, Ok("30".to_string())));
+        assert_eq!(actual, expected, "{}", msg);
+
+        let actual = answers
+            .verify(&record(4, 1, Err(Error::new("bad input"))))
+            .is_failure();
+        assert!(actual, "{}", msg);
+    }
+}
@@ -0,0 +1,119 @@
+//! Repeated solver timing and summary statistics
+use std::{fmt, time::Duration};
+
+/// run `f` `warmup` times, discarding the output, then return the output of `iterations`
+/// further runs
+///
+/// Stops at the first error
+pub fn repeat<T, E>(
+    warmup: usize,
+    iterations: usize,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> Result<Vec<T>, E> {
+    for _ in 0..warmup {
+        f()?;
+    }
+
+    (0..iterations).map(|_| f()).collect()
+}
+
+/// Summary statistics of a set of timing samples
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    /// Number of samples
+    pub runs: usize,
+    /// Fastest sample
+    pub min: Duration,
+    /// Median sample
+    pub median: Duration,
+    /// Arithmetic mean of the samples
+    pub mean: Duration,
+    /// Population standard deviation of the samples
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// summarize `samples`, returning `None` if there are none
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let runs = sorted.len();
+        // middle sample, or the mean of the middle pair
+        let median = (sorted[(runs - 1) / 2] + sorted[runs / 2]) / 2;
+
+        let mean = sorted.iter().sum::<Duration>() / runs as u32;
+        let variance = sorted
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+
+        Some(Self {
+            runs,
+            min: sorted[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:?}, median {:?}, mean {:?}, stddev {:?} ({} runs)",
+            self.min, self.median, self.mean, self.stddev, self.runs
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let msg = "should summarize the timing samples";
+        let samples = [9, 4, 4, 2, 5, 5, 7, 4].map(Duration::from_millis);
+        let actual = Stats::from_samples(&samples).unwrap();
+        assert_eq!(actual.runs, 8, "{}", msg);
+        assert_eq!(actual.min, Duration::from_millis(2), "{}", msg);
+        assert_eq!(actual.median, Duration::from_micros(4500), "{}", msg);
+        assert_eq!(actual.mean, Duration::from_millis(5), "{}", msg);
+        let error = actual.stddev.as_secs_f64() - 0.002;
+        assert!(error.abs() < 1e-9, "{}", msg);
+
+        let actual = Stats::from_samples(&[]);
+        assert_eq!(actual, None, "{}", msg);
+    }
+
+    #[test]
+    fn repeat_runs() {
+        let msg = "should warm up and then keep the output of each iteration";
+        let mut count = 0;
+        let actual = repeat(3, 5, || {
+            count += 1;
+            Ok::<_, ()>(count)
+        })
+        .unwrap();
+        assert_eq!(actual, vec![4, 5, 6, 7, 8], "{}", msg);
+
+        let msg = "should stop at the first error";
+        let mut count = 0;
+        let actual = repeat(3, 5, || {
+            count += 1;
+            if count == 5 {
+                Err(count)
+            } else {
+                Ok(count)
+            }
+        });
+        assert_eq!(actual, Err(5), "{}", msg);
+        assert_eq!(count, 5, "{}", msg);
+    }
+}
@@ -0,0 +1,381 @@
+//! Scaffold the module, sample input and registry entry for a new puzzle day
+//!
+//! Every step is skipped if already done, so the tool can be rerun safely.
+use std::{env, fs, path::Path, process};
+
+use advent_2023::{
+    fetch::{self, Curl, Fetcher},
+    FIRST_YEAR, YEAR,
+};
+
+/// file holding the adventofcode.com session cookie, relative to the package root
+const SESSION_FILE: &str = "adventofcode.session";
+
+/// module template, with `$YEAR`, `$DAY` (zero padded), `$NUMBER` and `$SOLUTION` placeholders
+const TEMPLATE: &str = r#"//! Solutions to $YEAR day $DAY problems
+//! --  --
+use crate::{read_file, Error, Solution};
+
+/// Returns the part one answer.
+pub fn solve_one(_input: &str) -> Result<usize, Error> {
+    Ok(0)
+}
+
+/// Returns the [`solve_one`] answer for the input file at `file_path`.
+pub fn one(file_path: &str) -> Result<usize, Error> {
+    solve_one(&read_file(file_path)?)
+}
+
+/// Returns the part two answer.
+pub fn solve_two(_input: &str) -> Result<usize, Error> {
+    Ok(0)
+}
+
+/// Returns the [`solve_two`] answer for the input file at `file_path`.
+pub fn two(file_path: &str) -> Result<usize, Error> {
+    solve_two(&read_file(file_path)?)
+}
+
+/// Day $NUMBER solutions
+$SOLUTION
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[ignore = "sample answer not filled in yet"]
+    fn part_one() {
+        let msg = "should return the part one answer";
+        let expected = 0;
+        let actual = one("input/$YEAR/$DAY-t.txt").unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    #[ignore = "sample answer not filled in yet"]
+    fn part_two() {
+        let msg = "should return the part two answer";
+        let expected = 0;
+        let actual = two("input/$YEAR/$DAY-t.txt").unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
+"#;
+
+/// check the requested puzzle day and event year
+fn validate(day: usize, year: usize) -> Result<(), String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("Invalid day {}, expected 1-25", day));
+    }
+    if year < FIRST_YEAR {
+        return Err(format!("Invalid event year {}", year));
+    }
+
+    Ok(())
+}
+
+/// module name for a day, prefixed with the year for events other than the crate's [`YEAR`]
+fn module_name(day: usize, year: usize) -> String {
+    if year == YEAR {
+        format!("day_{:02}", day)
+    } else {
+        format!("y{}_day_{:02}", year, day)
+    }
+}
+
+/// event year and day of a day module name, for ordering registry entries
+fn module_key(module: &str) -> Option<(usize, usize)> {
+    match module.split_once("_day_") {
+        Some((year, day)) => Some((year.strip_prefix('y')?.parse().ok()?, day.parse().ok()?)),
+        None => Some((YEAR, module.strip_prefix("day_")?.parse().ok()?)),
+    }
+}
+
+/// solution declaration for days of the crate's [`YEAR`]
+const SOLUTION: &str = r#"pub static SOLUTION: Solution<String> = crate::to_solution!(
+    $NUMBER,
+    "",
+    "$DAY-1",
+    (solve_one, "Part one"),
+    (solve_two, "Part two")
+);"#;
+
+/// solution declaration for days of other event years
+const YEAR_SOLUTION: &str = r#"pub static SOLUTION: Solution<String> = Solution {
+    year: $YEAR,
+    ..crate::to_solution!(
+        $NUMBER,
+        "",
+        "$DAY-1",
+        (solve_one, "Part one"),
+        (solve_two, "Part two")
+    )
+};"#;
+
+/// render the module template for a day
+fn render(day: usize, year: usize) -> String {
+    let solution = if year == YEAR {
+        SOLUTION
+    } else {
+        YEAR_SOLUTION
+    };
+
+    TEMPLATE
+        .replace("$SOLUTION", solution)
+        .replace("$YEAR", &year.to_string())
+        .replace("$DAY", &format!("{:02}", day))
+        .replace("$NUMBER", &day.to_string())
+}
+
+/// add a `pub mod` declaration to the sorted declarations in `lib`, or `None` if it is
+/// already declared
+fn insert_mod(lib: &str, module: &str) -> Option<String> {
+    let declaration = format!("pub mod {};", module);
+    if lib.lines().any(|line| line.trim() == declaration) {
+        return None;
+    }
+
+    let mut lines: Vec<_> = lib.lines().map(str::to_string).collect();
+    let declared: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let name = line.strip_prefix("pub mod ")?.strip_suffix(';')?;
+            Some((idx, name.to_string()))
+        })
+        .collect();
+    let position = declared
+        .iter()
+        .find(|(_, name)| name.as_str() > module)
+        .map(|(idx, _)| *idx)
+        .or_else(|| declared.last().map(|(idx, _)| idx + 1))
+        .unwrap_or(lines.len());
+    lines.insert(position, declaration);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// add a solution to the `DAYS` list in `registry` in year and day order, or `None` if it is
+/// already registered
+fn insert_registration(registry: &str, module: &str) -> Result<Option<String>, String> {
+    let entry = format!("&crate::{}::SOLUTION,", module);
+    if registry.lines().any(|line| line.trim() == entry) {
+        return Ok(None);
+    }
+    let key = module_key(module).ok_or_else(|| format!("Invalid module name '{}'", module))?;
+
+    let mut lines: Vec<_> = registry.lines().map(str::to_string).collect();
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("static DAYS"))
+        .ok_or("Missing DAYS list in registry.rs")?;
+    let end = lines[start..]
+        .iter()
+        .position(|line| line.trim() == "];")
+        .map(|idx| start + idx)
+        .ok_or("Unterminated DAYS list in registry.rs")?;
+    let position = (start + 1..end)
+        .find(|idx| {
+            let registered = lines[*idx]
+                .trim()
+                .strip_prefix("&crate::")
+                .and_then(|line| line.strip_suffix("::SOLUTION,"))
+                .and_then(module_key);
+            registered.is_some_and(|registered| registered > key)
+        })
+        .unwrap_or(end);
+    lines.insert(position, format!("    {}", entry));
+
+    Ok(Some(lines.join("\n") + "\n"))
+}
+
+/// write `contents` to `path` unless the file already exists
+fn create_file(path: &Path, contents: &str) -> Result<(), String> {
+    if path.exists() {
+        println!("exists:   {}", path.display());
+        return Ok(());
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("couldnt create {}: {}", dir.display(), err))?;
+    }
+    fs::write(path, contents)
+        .map_err(|err| format!("couldnt write {}: {}", path.display(), err))?;
+    println!("created:  {}", path.display());
+
+    Ok(())
+}
+
+/// apply `edit` to the file at `path`, writing it back if anything changed
+fn update_file(
+    path: &Path,
+    edit: impl FnOnce(&str) -> Result<Option<String>, String>,
+) -> Result<(), String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("couldnt read {}: {}", path.display(), err))?;
+    match edit(&contents)? {
+        Some(updated) => {
+            fs::write(path, updated)
+                .map_err(|err| format!("couldnt write {}: {}", path.display(), err))?;
+            println!("updated:  {}", path.display());
+        }
+        None => println!("current:  {}", path.display()),
+    }
+
+    Ok(())
+}
+
+/// create every file for a new day under `root`
+fn scaffold(root: &Path, day: usize, year: usize) -> Result<(), String> {
+    validate(day, year)?;
+    let module = module_name(day, year);
+    let input_dir = root.join("input").join(year.to_string());
+
+    create_file(
+        &root.join("src").join(&module).join("mod.rs"),
+        &render(day, year),
+    )?;
+    create_file(&input_dir.join(format!("{:02}-t.txt", day)), "")?;
+    create_file(
+        &input_dir.join(format!("{:02}-t.answers.toml", day)),
+        &format!("[day{:02}]\n", day),
+    )?;
+    update_file(&root.join("src").join("lib.rs"), |lib| {
+        Ok(insert_mod(lib, &module))
+    })?;
+    update_file(&root.join("src").join("registry.rs"), |registry| {
+        insert_registration(registry, &module)
+    })?;
+
+    fetch_input(root, day, year)
+}
+
+/// download the puzzle input into the input directory, if a session cookie is available
+fn fetch_input(root: &Path, day: usize, year: usize) -> Result<(), String> {
+    let session = match fetch::session(&root.join(SESSION_FILE)) {
+        Ok(session) => session,
+        Err(err) => {
+            println!("skipped:  puzzle input download ({})", err);
+            return Ok(());
+        }
+    };
+
+    let mut fetcher = Fetcher::new(Curl, session, root.join("input"));
+    let path = fetcher.cache_path(year, day);
+    if fetcher.is_cached(year, day) {
+        println!("exists:   {}", path.display());
+        return Ok(());
+    }
+    fetcher.fetch(year, day).map_err(|err| err.to_string())?;
+    println!("created:  {}", path.display());
+
+    Ok(())
+}
+
+/// print command line usage
+fn print_usage() {
+    eprintln!("Usage: scaffold <DAY> [YEAR]");
+    eprintln!("  creates src/day_NN/mod.rs, sample input files and the registry entry for a day");
+    eprintln!("  YEAR defaults to {}", YEAR);
+    eprintln!(
+        "  the puzzle input is downloaded using the session cookie in ${} or {}",
+        fetch::SESSION_VAR,
+        SESSION_FILE
+    );
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let parsed = match args.as_slice() {
+        [day] => day.parse().map(|day| (day, YEAR)),
+        [day, year] => day.parse().and_then(|day| Ok((day, year.parse()?))),
+        _ => {
+            print_usage();
+            process::exit(2);
+        }
+    };
+    let Ok((day, year)) = parsed else {
+        eprintln!("Invalid day or year: '{}'", args.join(" "));
+        print_usage();
+        process::exit(2);
+    };
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    if let Err(msg) = scaffold(root, day, year) {
+        eprintln!("{}", msg);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LIB: &str =
+        "mod error;\n\npub mod answers;\npub mod day_01;\npub mod day_04;\npub mod pool;\n";
+    const REGISTRY: &str = "\
+static DAYS: &[&dyn Day] = &[
+    &crate::day_01::SOLUTION,
+    &crate::day_04::SOLUTION,
+];
+";
+
+    #[test]
+    fn validate_day() {
+        let msg = "should accept puzzle days of an event year";
+        assert!(validate(25, 2022).is_ok(), "{}", msg);
+
+        let msg = "should reject days outside of december 1-25";
+        assert!(validate(0, YEAR).is_err(), "{}", msg);
+        assert!(validate(26, YEAR).is_err(), "{}", msg);
+
+        let msg = "should reject years before the first event";
+        assert!(validate(1, 2014).is_err(), "{}", msg);
+    }
+
+    #[test]
+    fn insert_module() {
+        let msg = "should insert the module declaration in sorted order";
+        let expected = "mod error;\n\npub mod answers;\npub mod day_01;\npub mod day_02;\npub mod day_04;\npub mod pool;\n";
+        let actual = insert_mod(LIB, "day_02").unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should not declare a module twice";
+        assert_eq!(insert_mod(expected, "day_02"), None, "{}", msg);
+    }
+
+    #[test]
+    fn register() {
+        let msg = "should register the day in year and day order";
+        let expected = "\
+static DAYS: &[&dyn Day] = &[
+    &crate::y2022_day_05::SOLUTION,
+    &crate::day_01::SOLUTION,
+    &crate::day_04::SOLUTION,
+    &crate::day_05::SOLUTION,
+];
+";
+        let actual = insert_registration(REGISTRY, "y2022_day_05")
+            .unwrap()
+            .unwrap();
+        let actual = insert_registration(&actual, "day_05").unwrap().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should not register a day twice";
+        let actual = insert_registration(expected, "day_04").unwrap();
+        assert_eq!(actual, None, "{}", msg);
+    }
+
+    #[test]
+    fn render_template() {
+        let msg = "should only override the year for other events";
+        let actual = render(5, YEAR);
+        assert!(!actual.contains("year:"), "{}", msg);
+        assert!(actual.contains("crate::to_solution!(\n    5,"), "{}", msg);
+        let actual = render(5, 2022);
+        assert!(actual.contains("year: 2022,"), "{}", msg);
+        assert!(actual.contains("input/2022/05-t.txt"), "{}", msg);
+    }
+}
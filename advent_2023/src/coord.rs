@@ -0,0 +1,282 @@
+//! Grid coordinates and compass directions
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::Error;
+
+/// Grid coordinate, as `Coord(row, column)` with rows increasing southward
+///
+/// Ordered row-major, by row and then by column
+#[derive(Clone, Copy, Default, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Coord(pub isize, pub isize);
+
+/// 3d grid coordinate, as `Coord3(x, y, z)`
+///
+/// Ordered by x, then y, then z
+#[derive(Clone, Copy, Default, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Coord3(pub isize, pub isize, pub isize);
+
+/// implement componentwise arithmetic for a coordinate tuple struct
+macro_rules! impl_coord_ops {
+    ($coord:ident, $($idx:tt),+) => {
+        impl Add for $coord {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self($(self.$idx + other.$idx),+)
+            }
+        }
+
+        impl Sub for $coord {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self($(self.$idx - other.$idx),+)
+            }
+        }
+
+        impl Neg for $coord {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self($(-self.$idx),+)
+            }
+        }
+
+        impl Mul<isize> for $coord {
+            type Output = Self;
+
+            fn mul(self, scale: isize) -> Self {
+                Self($(self.$idx * scale),+)
+            }
+        }
+
+        impl AddAssign for $coord {
+            fn add_assign(&mut self, other: Self) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $coord {
+            fn sub_assign(&mut self, other: Self) {
+                *self = *self - other;
+            }
+        }
+
+        impl $coord {
+            /// taxicab distance to `other`
+            pub fn manhattan(self, other: $coord) -> usize {
+                0 $(+ self.$idx.abs_diff(other.$idx))+
+            }
+
+            /// chessboard distance to `other`, counting diagonal steps as one
+            pub fn chebyshev(self, other: $coord) -> usize {
+                0 $(.max(self.$idx.abs_diff(other.$idx)))+
+            }
+        }
+    };
+}
+
+impl_coord_ops!(Coord, 0, 1);
+impl_coord_ops!(Coord3, 0, 1, 2);
+
+impl Coord {
+    /// Step one row up
+    pub const N: Coord = Coord(-1, 0);
+    /// Step one row up and one column right
+    pub const NE: Coord = Coord(-1, 1);
+    /// Step one column right
+    pub const E: Coord = Coord(0, 1);
+    /// Step one row down and one column right
+    pub const SE: Coord = Coord(1, 1);
+    /// Step one row down
+    pub const S: Coord = Coord(1, 0);
+    /// Step one row down and one column left
+    pub const SW: Coord = Coord(1, -1);
+    /// Step one column left
+    pub const W: Coord = Coord(0, -1);
+    /// Step one row up and one column left
+    pub const NW: Coord = Coord(-1, -1);
+
+    /// The orthogonal directions, clockwise from north
+    pub const ORTHOGONAL: [Coord; 4] = [Coord::N, Coord::E, Coord::S, Coord::W];
+
+    /// Every orthogonal and diagonal direction, clockwise from north
+    pub const DIRECTIONS: [Coord; 8] = [
+        Coord::N,
+        Coord::NE,
+        Coord::E,
+        Coord::SE,
+        Coord::S,
+        Coord::SW,
+        Coord::W,
+        Coord::NW,
+    ];
+
+    /// the orthogonally adjacent coordinates, clockwise from north
+    pub fn neighbors4(self) -> impl Iterator<Item = Coord> {
+        Coord::ORTHOGONAL
+            .into_iter()
+            .map(move |direction| self + direction)
+    }
+
+    /// the orthogonally and diagonally adjacent coordinates, clockwise from north
+    pub fn neighbors8(self) -> impl Iterator<Item = Coord> {
+        Coord::DIRECTIONS
+            .into_iter()
+            .map(move |direction| self + direction)
+    }
+
+    /// rotate a direction a quarter turn clockwise about the origin
+    pub fn rotate_cw(self) -> Coord {
+        Coord(self.1, -self.0)
+    }
+
+    /// rotate a direction a quarter turn counterclockwise about the origin
+    pub fn rotate_ccw(self) -> Coord {
+        Coord(-self.1, self.0)
+    }
+}
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+impl From<(isize, isize)> for Coord {
+    fn from(value: (isize, isize)) -> Self {
+        Coord(value.0, value.1)
+    }
+}
+
+impl From<(usize, usize)> for Coord {
+    fn from(value: (usize, usize)) -> Self {
+        Coord(value.0 as isize, value.1 as isize)
+    }
+}
+
+impl TryFrom<Coord> for (usize, usize) {
+    type Error = Error;
+
+    fn try_from(coord: Coord) -> Result<Self, Self::Error> {
+        match (usize::try_from(coord.0), usize::try_from(coord.1)) {
+            (Ok(row), Ok(column)) => Ok((row, column)),
+            _ => Err(Error::new(format!("Negative coordinate {}", coord))),
+        }
+    }
+}
+
+impl Coord3 {
+    /// The unit steps along each axis, positive then negative
+    pub const AXES: [Coord3; 6] = [
+        Coord3(1, 0, 0),
+        Coord3(0, 1, 0),
+        Coord3(0, 0, 1),
+        Coord3(-1, 0, 0),
+        Coord3(0, -1, 0),
+        Coord3(0, 0, -1),
+    ];
+
+    /// the coordinates sharing a face with this one
+    pub fn neighbors6(self) -> impl Iterator<Item = Coord3> {
+        Coord3::AXES.into_iter().map(move |axis| self + axis)
+    }
+}
+
+impl fmt::Display for Coord3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.0, self.1, self.2)
+    }
+}
+
+impl From<(isize, isize, isize)> for Coord3 {
+    fn from(value: (isize, isize, isize)) -> Self {
+        Coord3(value.0, value.1, value.2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn neighbors() {
+        let msg = "should list the orthogonal neighbors clockwise from north";
+        let expected = vec![Coord(1, 2), Coord(2, 3), Coord(3, 2), Coord(2, 1)];
+        let actual: Vec<_> = Coord(2, 2).neighbors4().collect();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should list all 8 neighbors";
+        let actual: Vec<_> = Coord(2, 2).neighbors8().collect();
+        assert_eq!(actual.len(), 8, "{}", msg);
+        let actual = actual.iter().all(|coord| coord.chebyshev(Coord(2, 2)) == 1);
+        assert!(actual, "{}", msg);
+    }
+
+    #[test]
+    fn rotate() {
+        let msg = "should turn a direction clockwise";
+        let expected = [Coord::E, Coord::S, Coord::W, Coord::N];
+        let actual = Coord::ORTHOGONAL.map(Coord::rotate_cw);
+        assert_eq!(actual, expected, "{}", msg);
+        assert_eq!(Coord::NE.rotate_cw(), Coord::SE, "{}", msg);
+
+        let msg = "should turn a direction counterclockwise";
+        let expected = [Coord::W, Coord::N, Coord::E, Coord::S];
+        let actual = Coord::ORTHOGONAL.map(Coord::rotate_ccw);
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn distance() {
+        let msg = "should measure taxicab and chessboard distance";
+        assert_eq!(Coord(1, -2).manhattan(Coord(-3, 4)), 10, "{}", msg);
+        assert_eq!(Coord(1, -2).chebyshev(Coord(-3, 4)), 6, "{}", msg);
+        assert_eq!(Coord3(1, -2, 0).manhattan(Coord3(-3, 4, 2)), 12, "{}", msg);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let msg = "should apply componentwise arithmetic";
+        let mut actual = Coord(1, -2) * 3;
+        assert_eq!(actual, Coord(3, -6), "{}", msg);
+        actual += Coord::S;
+        assert_eq!(actual, Coord(4, -6), "{}", msg);
+        actual -= Coord(4, 4);
+        assert_eq!(-actual, Coord(0, 10), "{}", msg);
+        assert_eq!(
+            -Coord3(1, 2, 3) + Coord3(1, 1, 1),
+            Coord3(0, -1, -2),
+            "{}",
+            msg
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        let msg = "should order coordinates row-major";
+        let mut actual = vec![Coord(1, 0), Coord(0, 2), Coord(0, -1)];
+        actual.sort();
+        assert_eq!(
+            actual,
+            vec![Coord(0, -1), Coord(0, 2), Coord(1, 0)],
+            "{}",
+            msg
+        );
+    }
+
+    #[test]
+    fn conversion() {
+        let msg = "should convert to and from unsigned pairs";
+        assert_eq!(Coord::from((3_usize, 4_usize)), Coord(3, 4), "{}", msg);
+        let actual = <(usize, usize)>::try_from(Coord(3, 4)).unwrap();
+        assert_eq!(actual, (3, 4), "{}", msg);
+
+        let msg = "should reject negative coordinates";
+        let actual = <(usize, usize)>::try_from(Coord(3, -4)).unwrap_err().reason;
+        assert_eq!(actual, "Negative coordinate (3, -4)", "{}", msg);
+    }
+}
@@ -5,23 +5,33 @@ use std::str::FromStr;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::read_file;
+use crate::{parse_lines, read_file, Error, Solution};
 
-fn parse_line(line: &str) -> usize {
+fn parse_line(line: &str) -> Result<usize, String> {
     let digits = line
         .chars()
         .filter(|char| char.is_ascii_digit())
         .collect::<Vec<_>>();
 
-    format!("{}{}", digits.first().unwrap(), digits.last().unwrap())
+    let (first, last) = digits
+        .first()
+        .zip(digits.last())
+        .ok_or_else(|| format!("No digits found in '{}'", line))?;
+
+    format!("{}{}", first, last)
         .parse()
-        .unwrap()
+        .map_err(|err| format!("Failed to parse {}{}: {}", first, last, err))
 }
 
 /// Returns the sum of all of the calibration values. Calibration values can be found by combining
 /// the first digit and the last digit (in that order) to form a single two-digit number.
-pub fn one(file_path: &str) -> usize {
-    read_file(file_path).lines().map(parse_line).sum::<usize>()
+pub fn solve_one(input: &str) -> Result<usize, Error> {
+    parse_lines(input, parse_line).sum()
+}
+
+/// Returns the [`solve_one`] answer for the input file at `file_path`.
+pub fn one(file_path: &str) -> Result<usize, Error> {
+    solve_one(&read_file(file_path)?)
 }
 
 lazy_static! {
@@ -58,35 +68,47 @@ impl FromStr for Digit {
     }
 }
 
-fn parse_two(line: &str) -> usize {
+fn parse_two(line: &str) -> Result<usize, String> {
     let first_digit = DIGIT_RE
         .captures_iter(line)
         .next()
         .map(|digi| digi.get(1).unwrap().into())
         .map(Digit::from_str)
-        .unwrap()
-        .unwrap()
+        .ok_or_else(|| format!("No digits found in '{}'", line))??
         .0;
     let last_digit = END_DIGIT_RE
         .captures_iter(line)
         .last()
         .map(|digi| digi.get(1).unwrap().into())
         .map(Digit::from_str)
-        .unwrap_or(Ok(Digit(first_digit)))
-        .unwrap()
+        .unwrap_or(Ok(Digit(first_digit)))?
         .0;
 
-    (first_digit * 10) + last_digit
+    Ok((first_digit * 10) + last_digit)
 }
 
 /// Returns the sum of all of the calibration values. Calibration values can be found by combining
 /// the first digit and the last digit (in that order) to form a single two-digit number. Some of
 /// the digits are actually spelled out with letters: one, two, three, four, five, six, seven,
 /// eight, and nine also count as valid "digits".
-pub fn two(file_path: &str) -> usize {
-    read_file(file_path).lines().map(parse_two).sum()
+pub fn solve_two(input: &str) -> Result<usize, Error> {
+    parse_lines(input, parse_two).sum()
+}
+
+/// Returns the [`solve_two`] answer for the input file at `file_path`.
+pub fn two(file_path: &str) -> Result<usize, Error> {
+    solve_two(&read_file(file_path)?)
 }
 
+/// Day 1 solutions
+pub static SOLUTION: Solution<String> = crate::to_solution!(
+    1,
+    "Trebuchet?!",
+    "01-1",
+    (solve_one, "Calibration values"),
+    (solve_two, "Calibration values redux")
+);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -95,7 +117,7 @@ mod test {
     fn part_one() {
         let msg = "should return the sum of all of the calibration values";
         let expected = 142;
-        let actual = one("input/01-t.txt");
+        let actual = one("input/2023/01-t.txt").unwrap();
         assert_eq!(actual, expected, "{}", msg);
     }
 
@@ -103,7 +125,7 @@ mod test {
     fn parse_part_two() {
         let msg = "should parse a line with a single digit";
         let expected = 99;
-        let actual = parse_two("hzfplpdt9");
+        let actual = parse_two("hzfplpdt9").unwrap();
         assert_eq!(actual, expected, "{}", msg);
     }
 
@@ -111,7 +133,7 @@ mod test {
     fn handle_overlaps() {
         let msg = "should parse the last digit with character overlaps";
         let expected = 82;
-        let actual = parse_two("8ninefivegzk7ftqbceightwogfv");
+        let actual = parse_two("8ninefivegzk7ftqbceightwogfv").unwrap();
         assert_eq!(actual, expected, "{}", msg);
     }
 
@@ -119,7 +141,18 @@ mod test {
     fn part_two() {
         let msg = "should return the sum of all of the calibration values";
         let expected = 281;
-        let actual = two("input/01-t2.txt");
+        let actual = two("input/2023/01-t2.txt").unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn malformed_line() {
+        let msg = "should report the line number of a line without digits";
+        let expected = Some(2);
+        let actual = parse_lines("1abc2\nabcdef\n", parse_line)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err()
+            .line;
         assert_eq!(actual, expected, "{}", msg);
     }
 }
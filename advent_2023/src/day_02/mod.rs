@@ -1,43 +1,80 @@
 //! Solutions to 2023 day 02 problems
 //! --- Day 2: Cube Conundrum ---
+use parser::three::lib::{choice, keep_first, keep_second, opt, p_string, spaces1, Parser};
 
-use crate::read_file;
+use crate::{
+    parse::{comma_list, delimited_list, labeled, p_usize, parse_lines_expecting},
+    read_file, Error, ParseError, Solution,
+};
 
+/// Count of each cube color revealed from the bag.
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
-struct GameInfo {
+pub struct GameInfo {
+    /// Red cube count
     pub red: usize,
+    /// Blue cube count
     pub blue: usize,
+    /// Green cube count
     pub green: usize,
 }
 
-fn parse_game(str: &str) -> Vec<GameInfo> {
-    let (_game_number, data) = str.split_once(':').unwrap();
-    data.split(';')
-        .map(|game_str| {
-            game_str.split(',').fold(GameInfo::default(), |acc, block| {
-                let mut parts = block.split_whitespace();
-                let count_str = parts.next().unwrap();
-                let count = count_str.parse::<usize>().unwrap();
-
-                let color = parts.last().unwrap();
-                match color {
-                    "red" => GameInfo {
-                        red: acc.red + count,
-                        ..acc
-                    },
-                    "blue" => GameInfo {
-                        blue: acc.blue + count,
-                        ..acc
-                    },
-                    "green" => GameInfo {
-                        green: acc.green + count,
-                        ..acc
-                    },
-                    _ => acc,
-                }
+/// A game's ID and the cube sets revealed in it.
+pub type Game = (usize, Vec<GameInfo>);
+
+/// Parse one revealed set of cubes, eg `3 blue, 4 red`, or `None` for a set which ends before
+/// a cube color.
+fn p_cube_set() -> Parser<Option<GameInfo>> {
+    let color = choice([
+        p_string("red").map(|_| GameInfo {
+            red: 1,
+            ..Default::default()
+        }),
+        p_string("blue").map(|_| GameInfo {
+            blue: 1,
+            ..Default::default()
+        }),
+        p_string("green").map(|_| GameInfo {
+            green: 1,
+            ..Default::default()
+        }),
+    ]);
+    // an unknown color is left unparsed, so it's reported at its own column
+    let cubes = keep_first(p_usize(), spaces1())
+        .and_then(opt(color))
+        .map(|(count, unit)| {
+            unit.map(|unit| GameInfo {
+                red: unit.red * count,
+                blue: unit.blue * count,
+                green: unit.green * count,
             })
-        })
-        .collect()
+        });
+
+    comma_list(cubes).map(|cubes| {
+        cubes
+            .into_iter()
+            .try_fold(GameInfo::default(), |acc, cubes| {
+                let cubes = cubes?;
+                Some(GameInfo {
+                    red: acc.red + cubes.red,
+                    blue: acc.blue + cubes.blue,
+                    green: acc.green + cubes.green,
+                })
+            })
+    })
+}
+
+/// Parse a game line, eg `Game 1: 3 blue, 4 red; 1 red, 2 green`, or `None` for a line which
+/// ends before a cube color.
+fn p_game() -> Parser<Option<Game>> {
+    let game_id = keep_second(p_string("Game").and_then(spaces1()), p_usize());
+
+    labeled(game_id, delimited_list(p_cube_set(), ';'))
+        .map(|(id, sets)| Some((id, sets.into_iter().collect::<Option<_>>()?)))
+}
+
+/// Parse the game record, a list of the revealed cube sets for each game ID.
+pub fn parse_games(input: &str) -> Result<Vec<Game>, ParseError> {
+    parse_lines_expecting(input, &p_game(), "a cube color").collect()
 }
 
 fn is_possible(max: GameInfo, game: &[GameInfo]) -> bool {
@@ -46,11 +83,14 @@ fn is_possible(max: GameInfo, game: &[GameInfo]) -> bool {
 }
 
 /// Returns the sum of the IDs of possible games.
-pub fn one(file_path: &str) -> usize {
-    read_file(file_path)
-        .lines()
-        .map(parse_game)
-        .enumerate()
+pub fn solve_one(input: &str) -> Result<usize, Error> {
+    Ok(possible_id_sum(parse_games(input)?))
+}
+
+/// Returns the sum of the IDs of possible games in the parsed game record.
+pub fn possible_id_sum(games: Vec<Game>) -> usize {
+    games
+        .iter()
         .filter(|(_, game_data)| {
             is_possible(
                 GameInfo {
@@ -61,16 +101,26 @@ pub fn one(file_path: &str) -> usize {
                 game_data,
             )
         })
-        .fold(0, |acc, (idx, _)| acc + (idx + 1))
+        .map(|(id, _)| id)
+        .sum()
+}
+
+/// Returns the [`solve_one`] answer for the input file at `file_path`.
+pub fn one(file_path: &str) -> Result<usize, Error> {
+    solve_one(&read_file(file_path)?)
 }
 
 /// Returns the sum of the minimum number of red, green, and blue cubes in each game multiplied
 /// together.
-pub fn two(file_path: &str) -> usize {
-    read_file(file_path)
-        .lines()
-        .map(parse_game)
-        .map(|game_data| {
+pub fn solve_two(input: &str) -> Result<usize, Error> {
+    Ok(power_sum(parse_games(input)?))
+}
+
+/// Returns the sum of the minimum cube set powers of the parsed game record.
+pub fn power_sum(games: Vec<Game>) -> usize {
+    games
+        .into_iter()
+        .map(|(_, game_data)| {
             game_data
                 .into_iter()
                 .fold(GameInfo::default(), |acc, game| GameInfo {
@@ -83,6 +133,21 @@ pub fn two(file_path: &str) -> usize {
         .sum()
 }
 
+/// Returns the [`solve_two`] answer for the input file at `file_path`.
+pub fn two(file_path: &str) -> Result<usize, Error> {
+    solve_two(&read_file(file_path)?)
+}
+
+/// Day 2 solutions
+pub static SOLUTION: Solution<String> = crate::to_solution!(
+    2,
+    "Cube Conundrum",
+    "02-1",
+    parse_games,
+    (possible_id_sum, "Possible games"),
+    (power_sum, "Power of cubes")
+);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -91,7 +156,7 @@ mod test {
     fn part_one() {
         let msg = "should return the sum of the IDs of possible games";
         let expected = 8;
-        let actual = one("input/02-t.txt");
+        let actual = one("input/2023/02-t.txt").unwrap();
         assert_eq!(actual, expected, "{}", msg);
     }
 
@@ -99,7 +164,30 @@ mod test {
     fn part_two() {
         let msg = "should return the sum of the 'power' of the minimum cube set";
         let expected = 2286;
-        let actual = two("input/02-t.txt");
+        let actual = two("input/2023/02-t.txt").unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn malformed_line() {
+        let msg = "should locate an unknown cube color";
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 red, 2 purple";
+        let actual = parse_games(input).unwrap_err();
+        assert_eq!((actual.line, actual.column), (2, 18), "{}", msg);
+        assert_eq!(actual.snippet, "Game 2: 1 red, 2 purple", "{}", msg);
+
+        let msg = "should locate a misspelled cube color";
+        let actual = parse_games("Game 2: 3 bleu, 4 red").unwrap_err();
+        assert_eq!(actual.column, 11, "{}", msg);
+        assert_eq!(actual.reason, "Unexpected 'bleu, 4 red'", "{}", msg);
+    }
+
+    #[test]
+    fn game_ids() {
+        let msg = "should sum the IDs of possible games, not their positions";
+        let input = "Game 3: 3 blue, 4 red\nGame 7: 20 red\nGame 10: 1 green";
+        let expected = 13;
+        let actual = solve_one(input).unwrap();
         assert_eq!(actual, expected, "{}", msg);
     }
 }
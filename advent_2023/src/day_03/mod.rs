@@ -1,144 +1,109 @@
 //! Solutions to 2023 day 03 problems
 //! --- Day 3: Gear Ratios ---
-use std::collections::{HashMap, HashSet};
-
-use crate::{read_file, Coord};
+use crate::{read_file, Coord, Error, Grid, Solution};
 
+/// A part number and its location in the schematic.
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
-struct Num {
+pub struct Num {
     row: usize,
     start: usize,
     end: usize,
     value: usize,
 }
 
-/// Parse a schematic to locate symbols, digits, and complete numbers.
-fn parse_schematic(file: &str) -> (HashMap<Coord, char>, HashSet<Coord>, Vec<Num>) {
-    let mut symbols = HashMap::new();
-    let mut digits = HashSet::new();
+impl Num {
+    /// Every location covered by the number's digits.
+    fn coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        (self.start..=self.end).map(|col| Coord::from((self.row, col)))
+    }
+
+    /// Whether `coord` touches the number, including diagonally.
+    fn is_adjacent(&self, coord: Coord) -> bool {
+        self.coords().any(|digit| digit.chebyshev(coord) <= 1)
+    }
+}
+
+/// Schematic grid and its complete numbers.
+pub type Schematic = (Grid<char>, Vec<Num>);
+
+/// Whether a schematic cell holds a symbol.
+fn is_symbol(ch: &char) -> bool {
+    *ch != '.' && !ch.is_ascii_digit()
+}
+
+/// Parse a schematic to locate its complete numbers.
+pub fn parse_schematic(file: &str) -> Result<Schematic, Error> {
+    let grid: Grid<char> = Grid::from_text(file)?;
     let mut numbers = Vec::new();
 
-    for (row, line) in file.lines().enumerate() {
-        let mut start = None;
-        let mut end = None;
-        let mut save_num = |start: &mut Option<usize>, end: &mut Option<usize>| {
-            if let Some(start) = start.take() {
-                let end = end.take().unwrap();
-                numbers.push(Num {
-                    row,
-                    start,
-                    end,
-                    value: line[start..=end].parse::<usize>().unwrap(),
-                })
+    for (row, line) in grid.rows().enumerate() {
+        let mut col = 0;
+        while col < line.len() {
+            if !line[col].is_ascii_digit() {
+                col += 1;
+                continue;
             }
-        };
-
-        for (col, ch) in line.chars().enumerate() {
-            match ch {
-                '.' => save_num(&mut start, &mut end),
-                ch if ch.is_ascii_digit() => {
-                    if start.is_some() {
-                        end = end.map(|x| x + 1);
-                    } else {
-                        start = Some(col);
-                        end = Some(col);
-                    }
-
-                    digits.insert((row as isize, col as isize).into());
-                }
-                _ => {
-                    save_num(&mut start, &mut end);
-                    symbols.insert((row as isize, col as isize).into(), ch);
-                }
-            };
-        }
 
-        save_num(&mut start, &mut end);
+            let start = col;
+            while col < line.len() && line[col].is_ascii_digit() {
+                col += 1;
+            }
+            let digits: String = line[start..col].iter().collect();
+            let value = digits.parse::<usize>().map_err(|err| {
+                Error::at_line(row + 1, format!("Invalid number '{}': {}", digits, err))
+            })?;
+            numbers.push(Num {
+                row,
+                start,
+                end: col - 1,
+                value,
+            });
+        }
     }
 
-    (symbols, digits, numbers)
+    Ok((grid, numbers))
 }
 
 /// Returns the sum of the engine part numbers.
-pub fn one(file_path: &str) -> usize {
-    let input = read_file(file_path);
-    let mut result = Vec::new();
-
-    let (symbols, mut digits, mut numbers) = parse_schematic(&input);
-    for symbol in symbols.into_keys() {
-        // check neighbors for digit
-        [
-            //prev row
-            Coord(-1, 0),
-            Coord(-1, 1),
-            Coord(-1, -1),
-            // current row
-            Coord(0, 1),
-            Coord(0, -1),
-            // next row
-            Coord(1, -1),
-            Coord(1, 0),
-            Coord(1, 1),
-        ]
+pub fn solve_one(input: &str) -> Result<usize, Error> {
+    Ok(part_number_sum(parse_schematic(input)?))
+}
+
+/// Returns the sum of the engine part numbers in the parsed schematic.
+pub fn part_number_sum(schematic: Schematic) -> usize {
+    let (grid, numbers) = schematic;
+    numbers
         .iter()
-        .filter_map(|offset| {
-            let location = *offset + symbol;
-            if digits.remove(&location) {
-                Some(location)
-            } else {
-                None
-            }
+        .filter(|num| {
+            num.coords()
+                .flat_map(|digit| grid.neighbors8(digit))
+                .any(|coord| is_symbol(&grid[coord]))
         })
-        .for_each(|coord| {
-            let parts: (Vec<Num>, Vec<Num>) = numbers.iter().partition(|num| {
-                num.row == coord.0 as usize && (num.start..=num.end).contains(&(coord.1 as usize))
-            });
-
-            numbers = parts.1;
-            result.extend(parts.0.into_iter().map(|num| num.value));
-        });
-    }
+        .map(|num| num.value)
+        .sum()
+}
 
-    result.iter().sum()
+/// Returns the [`solve_one`] answer for the input file at `file_path`.
+pub fn one(file_path: &str) -> Result<usize, Error> {
+    solve_one(&read_file(file_path)?)
 }
 
 /// Returns the sum of the gear ratios.
-pub fn two(file_path: &str) -> usize {
-    let input = read_file(file_path);
-    let (symbols, mut digits, mut numbers) = parse_schematic(&input);
-    symbols
-        .into_iter()
-        .filter(|(_, char)| *char == '*')
+pub fn solve_two(input: &str) -> Result<usize, Error> {
+    Ok(gear_ratio_sum(parse_schematic(input)?))
+}
+
+/// Returns the sum of the gear ratios in the parsed schematic.
+pub fn gear_ratio_sum(schematic: Schematic) -> usize {
+    let (grid, numbers) = schematic;
+    grid.iter_coords()
+        .filter(|(_, ch)| **ch == '*')
         .map(|(coord, _)| {
-            let neighbors = [
-                //prev row
-                Coord(-1, 0),
-                Coord(-1, 1),
-                Coord(-1, -1),
-                // current row
-                Coord(0, 1),
-                Coord(0, -1),
-                // next row
-                Coord(1, -1),
-                Coord(1, 0),
-                Coord(1, 1),
-            ]
-            .into_iter()
-            .map(|offset| offset + coord)
-            .filter(|location| digits.remove(location))
-            .filter_map(|location| {
-                if let Some(idx) = numbers.iter().position(|num| {
-                    num.row == location.0 as usize
-                        && (num.start..=num.end).contains(&(location.1 as usize))
-                }) {
-                    let number = numbers.remove(idx).value;
-                    println!("    number {:?}", number);
-                    Some(number)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<_>>();
+            let neighbors = numbers
+                .iter()
+                .filter(|num| num.is_adjacent(coord))
+                .map(|num| num.value)
+                .collect::<Vec<_>>();
 
             if neighbors.len() == 2 {
                 neighbors.iter().product()
@@ -149,6 +114,21 @@ pub fn two(file_path: &str) -> usize {
         .sum()
 }
 
+/// Returns the [`solve_two`] answer for the input file at `file_path`.
+pub fn two(file_path: &str) -> Result<usize, Error> {
+    solve_two(&read_file(file_path)?)
+}
+
+/// Day 3 solutions
+pub static SOLUTION: Solution<String> = crate::to_solution!(
+    3,
+    "Gear Ratios",
+    "03-1",
+    parse_schematic,
+    (part_number_sum, "Part number search"),
+    (gear_ratio_sum, "Gear ratio sum")
+);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -157,7 +137,7 @@ mod test {
     fn part_one() {
         let msg = "should return the sum of the engine part numbers";
         let expected = 4361;
-        let actual = one("input/03-t.txt");
+        let actual = one("input/2023/03-t.txt").unwrap();
         assert_eq!(actual, expected, "{}", msg);
     }
 
@@ -165,7 +145,7 @@ mod test {
     fn part_two() {
         let msg = "should return the sum of the gear ratios";
         let expected = 467835;
-        let actual = two("input/03-t.txt");
+        let actual = two("input/2023/03-t.txt").unwrap();
         assert_eq!(actual, expected, "{}", msg);
     }
 }
@@ -1,49 +1,67 @@
 //! Solutions to 2023 day 04 problems
 //! --- Day 4: Scratchcards ---
-use crate::read_file;
+use parser::three::lib::{keep_second, opt, p_char, p_string, spaces, spaces1, Parser};
 
-fn parse_line(line: &str) -> (Vec<usize>, Vec<usize>) {
-    let parse_numbers = |num_str: &str| -> Vec<usize> {
-        num_str
-            .split_whitespace()
-            .map(|num_str| num_str.parse())
-            .collect::<Result<Vec<_>, _>>()
-            .unwrap()
-    };
+use crate::{
+    parse::{ints, labeled, p_usize, parse_lines_expecting},
+    read_file, Error, ParseError, Solution,
+};
 
-    let (left, have_str) = line.split_once(" | ").unwrap();
-    let (_game, win_str) = left.split_once(": ").unwrap();
+/// A scratchcard's winning numbers and the numbers you have.
+pub type Card = (Vec<usize>, Vec<usize>);
 
-    (parse_numbers(win_str), parse_numbers(have_str))
+/// Parse a card line, eg `Card 1: 41 48 83 | 83 86  6`, or `None` for a line which ends
+/// before its `|`.
+fn p_card() -> Parser<Option<Card>> {
+    let card_id = p_string("Card").and_then(spaces1()).and_then(p_usize());
+    let have = keep_second(spaces().and_then(p_char('|')), ints());
+    let numbers = ints()
+        .and_then(opt(have))
+        .map(|(winners, have)| Some((winners, have?)));
+
+    labeled(card_id, numbers).map(|(_, card)| card)
+}
+
+/// Parse the pile of scratchcards.
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse_lines_expecting(input, &p_card(), "'|'").collect()
 }
 
 /// Return the point total of the winning cards.
-pub fn one(file_path: &str) -> usize {
-    read_file(file_path)
-        .lines()
-        .map(parse_line)
+pub fn solve_one(input: &str) -> Result<usize, Error> {
+    Ok(points(parse_cards(input)?))
+}
+
+/// Return the point total of the parsed winning cards.
+pub fn points(cards: Vec<Card>) -> usize {
+    cards
+        .into_iter()
         .map(|(winners, have)| {
-            let count: u32 = have
-                .into_iter()
-                .filter(|n| winners.contains(n))
-                .count()
-                .try_into()
-                .unwrap();
+            let count = have.into_iter().filter(|n| winners.contains(n)).count();
 
             if count == 0 {
                 return 0;
             }
 
-            2_usize.pow(count - 1)
+            1 << (count - 1)
         })
         .sum()
 }
 
+/// Returns the [`solve_one`] answer for the input file at `file_path`.
+pub fn one(file_path: &str) -> Result<usize, Error> {
+    solve_one(&read_file(file_path)?)
+}
+
 /// Returns the total number of scratchcards.
-pub fn two(file_path: &str) -> usize {
-    let win_list: Vec<_> = read_file(file_path)
-        .lines()
-        .map(parse_line)
+pub fn solve_two(input: &str) -> Result<usize, Error> {
+    Ok(card_count(parse_cards(input)?))
+}
+
+/// Returns the total number of scratchcards won from the parsed cards.
+pub fn card_count(cards: Vec<Card>) -> usize {
+    let win_list: Vec<_> = cards
+        .into_iter()
         .map(|(winners, have)| have.into_iter().filter(|n| winners.contains(n)).count())
         .collect();
     let mut cards = vec![1_usize; win_list.len()];
@@ -56,6 +74,21 @@ pub fn two(file_path: &str) -> usize {
     cards.iter().sum()
 }
 
+/// Returns the [`solve_two`] answer for the input file at `file_path`.
+pub fn two(file_path: &str) -> Result<usize, Error> {
+    solve_two(&read_file(file_path)?)
+}
+
+/// Day 4 solutions
+pub static SOLUTION: Solution<String> = crate::to_solution!(
+    4,
+    "Scratchcards",
+    "04-1",
+    parse_cards,
+    (points, "Scratch card points"),
+    (card_count, "Card count")
+);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -64,7 +97,7 @@ mod test {
     fn part_one() {
         let msg = "should return the point total of the winning cards";
         let expected = 13;
-        let actual = one("input/04-t.txt");
+        let actual = one("input/2023/04-t.txt").unwrap();
         assert_eq!(actual, expected, "{}", msg);
     }
 
@@ -72,7 +105,32 @@ mod test {
     fn part_two() {
         let msg = "should return the total number of scratchcards";
         let expected = 30;
-        let actual = two("input/04-t.txt");
+        let actual = two("input/2023/04-t.txt").unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn from_text() {
+        let msg = "should solve from puzzle input text";
+        let expected = 2;
+        let actual = solve_one("Card 1: 1 2 3 | 3 4 5 2").unwrap();
         assert_eq!(actual, expected, "{}", msg);
     }
+
+    #[test]
+    fn malformed_line() {
+        let msg = "should locate a malformed number";
+        let actual = parse_cards("Card 1: 1 | 2\nCard 2: 4 x | 5").unwrap_err();
+        assert_eq!((actual.line, actual.column), (2, 11), "{}", msg);
+        assert_eq!(actual.reason, "Unexpected 'x | 5'", "{}", msg);
+
+        let msg = "should report a card without its numbers";
+        let actual = parse_cards("Card 1: 1 2").unwrap_err();
+        assert_eq!(
+            (actual.column, actual.reason.as_str()),
+            (12, "Expected '|'"),
+            "{}",
+            msg
+        );
+    }
 }
@@ -0,0 +1,248 @@
+//! Crate error types
+use std::{fmt, time::Duration};
+
+/// Category of a failure to produce a solution
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// The solver returned an error
+    #[default]
+    Failed,
+    /// The solver panicked
+    Panic,
+    /// The solver overran its time limit
+    Timeout,
+}
+
+/// Failure to produce a solution, with whatever location context is known
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Error {
+    /// Puzzle day
+    pub day: Option<usize>,
+    /// Puzzle part
+    pub part: Option<usize>,
+    /// 1-based input line number
+    pub line: Option<usize>,
+    /// Description of the failure
+    pub reason: String,
+    /// Input parsing failure details, for rendering the offending input
+    pub parse: Option<Box<ParseError>>,
+    /// Category of the failure
+    pub kind: ErrorKind,
+}
+
+impl Error {
+    /// create an error with no location context
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+            ..Default::default()
+        }
+    }
+
+    /// create an error for the 1-based `line` of the input
+    pub fn at_line(line: usize, reason: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            ..Self::new(reason)
+        }
+    }
+
+    /// create an error for a solver which panicked with `message`
+    pub fn panic(message: impl fmt::Display) -> Self {
+        Self {
+            kind: ErrorKind::Panic,
+            ..Self::new(format!("PANIC: {}", message))
+        }
+    }
+
+    /// create an error for a solver which was still running after `limit`
+    pub fn timeout(limit: Duration) -> Self {
+        Self {
+            kind: ErrorKind::Timeout,
+            ..Self::new(format!("TIMEOUT after {}s", limit.as_secs_f64()))
+        }
+    }
+
+    /// attach the puzzle day
+    pub fn with_day(self, day: usize) -> Self {
+        Self {
+            day: Some(day),
+            ..self
+        }
+    }
+
+    /// attach the puzzle part
+    pub fn with_part(self, part: usize) -> Self {
+        Self {
+            part: Some(part),
+            ..self
+        }
+    }
+
+    /// attach the input file name to a parsing failure
+    pub fn with_file(self, file: &str) -> Self {
+        Self {
+            parse: self.parse.map(|parse| Box::new(parse.with_file(file))),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let location = [
+            self.day.map(|day| format!("Day {:02}", day)),
+            self.part.map(|part| format!("part {}", part)),
+            self.line.map(|line| format!("line {}", line)),
+            self.parse
+                .as_ref()
+                .map(|parse| format!("column {}", parse.column)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        if location.is_empty() {
+            write!(f, "{}", self.reason)
+        } else {
+            write!(f, "{}: {}", location.join(", "), self.reason)
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(parse: ParseError) -> Self {
+        Self {
+            line: Some(parse.line),
+            reason: parse.reason.clone(),
+            parse: Some(Box::new(parse)),
+            ..Default::default()
+        }
+    }
+}
+
+/// Unexpected puzzle input, with the location and text of the offending line
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ParseError {
+    /// Input file name, if known
+    pub file: Option<String>,
+    /// 1-based input line number
+    pub line: usize,
+    /// 1-based column number within the line
+    pub column: usize,
+    /// Text of the offending line
+    pub snippet: String,
+    /// Description of the failure
+    pub reason: String,
+}
+
+impl ParseError {
+    /// create an error at the 0-based byte `offset` into `input`
+    pub fn at_offset(input: &str, offset: usize, reason: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |idx| offset + idx);
+
+        Self {
+            file: None,
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// attach the input file name
+    pub fn with_file(self, file: &str) -> Self {
+        Self {
+            file: Some(file.to_string()),
+            ..self
+        }
+    }
+
+    /// render the offending line with a caret under the failing column
+    pub fn excerpt(&self) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let location = match &self.file {
+            Some(file) => format!("{}:{}:{}", file, self.line, self.column),
+            None => format!("line {}, column {}", self.line, self.column),
+        };
+
+        [
+            format!("{}--> {}", gutter, location),
+            format!("{} |", gutter),
+            format!("{} | {}", self.line, self.snippet),
+            format!(
+                "{} | {}^ {}",
+                gutter,
+                " ".repeat(self.column.saturating_sub(1)),
+                self.reason
+            ),
+        ]
+        .join("\n")
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(f, "{}:{}: {}", self.line, self.column, self.reason)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let msg = "should format the known location context";
+        let expected = "Day 04, part 2, line 7: bad card";
+        let actual = Error::at_line(7, "bad card")
+            .with_day(4)
+            .with_part(2)
+            .to_string();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = "bad card";
+        let actual = Error::new("bad card").to_string();
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn parse_error() {
+        let msg = "should locate the offending line and column";
+        let input = "Card 1: 1 2 | 3\nCard 2: 4 x | 5\n";
+        let actual = ParseError::at_offset(input, 26, "Unexpected 'x'");
+        assert_eq!((actual.line, actual.column), (2, 11), "{}", msg);
+        assert_eq!(actual.snippet, "Card 2: 4 x | 5", "{}", msg);
+
+        let msg = "should render a caret under the failing column";
+        let expected = [
+            " --> input/04-t.txt:2:11",
+            "  |",
+            "2 | Card 2: 4 x | 5",
+            "  |           ^ Unexpected 'x'",
+        ]
+        .join("\n");
+        let actual = actual.with_file("input/04-t.txt").excerpt();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should keep the column in the solver error";
+        let expected = "Day 04, line 2, column 11: Unexpected 'x'";
+        let actual = Error::from(ParseError::at_offset(input, 26, "Unexpected 'x'"))
+            .with_day(4)
+            .to_string();
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
@@ -0,0 +1,415 @@
+//! Puzzle input downloads, cached on disk in the runner's input directory layout
+//!
+//! Requests go through a [`Transport`], so the fetcher can be pointed at a local stand-in
+//! server instead of adventofcode.com.
+use std::{
+    env, fs,
+    io::{Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{Error, FIRST_YEAR};
+
+/// Advent of Code site url
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable holding the session cookie, overriding the session file
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Default minimum delay between requests to the site
+pub const MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// File in the cache directory holding the time of the last request, in nanoseconds since the
+/// unix epoch, so the minimum delay holds across runs
+const LAST_REQUEST_FILE: &str = ".last_request";
+
+/// Identifies the tool to the site operators, as they request
+const USER_AGENT: &str = concat!(
+    "advent_2023 input fetcher (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+/// Response to an HTTP request
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Response {
+    /// HTTP status code
+    pub status: u16,
+    /// Response body
+    pub body: String,
+}
+
+/// HTTP backend used to download inputs
+pub trait Transport {
+    /// send a GET request for `url`, authenticated with the `session` cookie
+    fn get(&self, url: &str, session: &str) -> Result<Response, Error>;
+}
+
+/// Transport shelling out to `curl`, for https urls
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Curl;
+
+impl Transport for Curl {
+    fn get(&self, url: &str, session: &str) -> Result<Response, Error> {
+        // the cookie is passed as config on stdin to keep it out of the process list
+        let mut child = Command::new("curl")
+            .args(["--silent", "--show-error", "--config", "-"])
+            .args([
+                "--user-agent",
+                USER_AGENT,
+                "--write-out",
+                "\n%{http_code}",
+                url,
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| Error::new(format!("couldnt run curl: {}", err)))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "cookie = \"session={}\"", session)
+                .map_err(|err| Error::new(format!("couldnt configure curl: {}", err)))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|err| Error::new(format!("couldnt run curl: {}", err)))?;
+        if !output.status.success() {
+            return Err(Error::new(format!(
+                "curl failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout
+            .rsplit_once('\n')
+            .ok_or_else(|| Error::new("curl returned no status code"))?;
+        let status = status.trim().parse().map_err(|_| {
+            Error::new(format!("curl returned an invalid status code '{}'", status))
+        })?;
+
+        Ok(Response {
+            status,
+            body: body.to_string(),
+        })
+    }
+}
+
+/// Minimal HTTP/1.1 transport over a plain TCP connection, for http urls
+///
+/// Responses are read until the server closes the connection, and chunked transfer encoding
+/// isn't supported.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Http;
+
+impl Transport for Http {
+    fn get(&self, url: &str, session: &str) -> Result<Response, Error> {
+        let invalid = || Error::new(format!("unsupported url '{}'", url));
+        let rest = url.strip_prefix("http://").ok_or_else(invalid)?;
+        let (host, path) = match rest.find('/') {
+            Some(idx) => rest.split_at(idx),
+            None => (rest, "/"),
+        };
+        let address = if host.contains(':') {
+            host.to_string()
+        } else {
+            format!("{}:80", host)
+        };
+
+        let io_err =
+            |err: std::io::Error| Error::new(format!("request to {} failed: {}", url, err));
+        let mut stream = TcpStream::connect(address).map_err(io_err)?;
+        write!(
+            stream,
+            "GET {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nCookie: session={}\r\nConnection: close\r\n\r\n",
+            path, host, USER_AGENT, session
+        )
+        .map_err(io_err)?;
+        let mut raw = String::new();
+        stream.read_to_string(&mut raw).map_err(io_err)?;
+
+        let (head, body) = raw
+            .split_once("\r\n\r\n")
+            .ok_or_else(|| Error::new(format!("malformed response from {}", url)))?;
+        let status = head
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .ok_or_else(|| Error::new(format!("malformed status line from {}", url)))?;
+
+        Ok(Response {
+            status,
+            body: body.to_string(),
+        })
+    }
+}
+
+/// read the session cookie from `AOC_SESSION`, or else the file at `path`
+pub fn session(path: &Path) -> Result<String, Error> {
+    let session = match env::var(SESSION_VAR) {
+        Ok(session) => session,
+        Err(_) => fs::read_to_string(path).map_err(|err| {
+            Error::new(format!(
+                "no session cookie in {} or {}: {}",
+                SESSION_VAR,
+                path.display(),
+                err
+            ))
+        })?,
+    };
+    let session = session.trim();
+    if session.is_empty() {
+        return Err(Error::new("session cookie is empty"));
+    }
+
+    Ok(session.to_string())
+}
+
+/// Downloads puzzle inputs, caching each in the input directory as `<YEAR>/NN-1.txt`
+#[derive(Debug)]
+pub struct Fetcher<T: Transport> {
+    transport: T,
+    session: String,
+    base_url: String,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+}
+
+impl<T: Transport> Fetcher<T> {
+    /// create a fetcher for adventofcode.com, caching inputs under `cache_dir`
+    pub fn new(transport: T, session: impl Into<String>, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            transport,
+            session: session.into(),
+            base_url: BASE_URL.to_string(),
+            cache_dir: cache_dir.into(),
+            min_interval: MIN_INTERVAL,
+        }
+    }
+
+    /// request inputs from the site at `base_url` instead
+    pub fn with_base_url(self, base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            ..self
+        }
+    }
+
+    /// wait at least `min_interval` between requests
+    pub fn with_min_interval(self, min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            ..self
+        }
+    }
+
+    /// path of the cached input for a puzzle day
+    pub fn cache_path(&self, year: usize, day: usize) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("{:02}-1.txt", day))
+    }
+
+    /// whether the input for a puzzle day has already been downloaded
+    pub fn is_cached(&self, year: usize, day: usize) -> bool {
+        self.cache_path(year, day).is_file()
+    }
+
+    /// the input for a puzzle day, downloading it if it isn't cached
+    pub fn fetch(&mut self, year: usize, day: usize) -> Result<String, Error> {
+        if !(1..=25).contains(&day) || year < FIRST_YEAR {
+            return Err(Error::new(format!("no puzzle for {} day {}", year, day)));
+        }
+        let path = self.cache_path(year, day);
+        if path.is_file() {
+            return fs::read_to_string(&path)
+                .map_err(|err| Error::new(format!("couldnt read {}: {}", path.display(), err)));
+        }
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = self.request(&url)?;
+        if response.body.contains("Please log in") {
+            return Err(Error::new(
+                "session cookie rejected, log in again and update the session cookie",
+            ));
+        }
+        if response.status != 200 {
+            let reason = response.body.lines().next().unwrap_or_default();
+            return Err(Error::new(format!(
+                "fetching {} failed with status {}: {}",
+                url, response.status, reason
+            )));
+        }
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| Error::new(format!("couldnt create {}: {}", dir.display(), err)))?;
+        }
+        fs::write(&path, &response.body)
+            .map_err(|err| Error::new(format!("couldnt write {}: {}", path.display(), err)))?;
+
+        Ok(response.body)
+    }
+
+    /// send a request, first waiting out the minimum interval since the last one by any fetcher
+    /// sharing the cache directory
+    fn request(&mut self, url: &str) -> Result<Response, Error> {
+        let path = self.cache_dir.join(LAST_REQUEST_FILE);
+        let last = fs::read_to_string(&path)
+            .ok()
+            .and_then(|nanos| nanos.trim().parse().ok())
+            .map(|nanos| UNIX_EPOCH + Duration::from_nanos(nanos));
+        if let Some(last) = last {
+            // a last request in the future means the clock went back, so wait the full interval
+            let elapsed = SystemTime::now().duration_since(last).unwrap_or_default();
+            thread::sleep(self.min_interval.saturating_sub(elapsed));
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        fs::create_dir_all(&self.cache_dir)
+            .and_then(|_| fs::write(&path, now.to_string()))
+            .map_err(|err| Error::new(format!("couldnt write {}: {}", path.display(), err)))?;
+
+        self.transport.get(url, &self.session)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::BufRead,
+        io::BufReader,
+        net::TcpListener,
+        sync::mpsc::{self, Receiver},
+        time::Instant,
+    };
+
+    use super::*;
+
+    const LOGIN_PAGE: &str =
+        "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+
+    /// serve `responses` in order from a local stand-in server, sending back each request head
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, requests) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let head: Vec<_> = BufReader::new(&stream)
+                    .lines()
+                    .map_while(Result::ok)
+                    .take_while(|line| !line.is_empty())
+                    .collect();
+                sender.send(head.join("\n")).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {} OK\r\nContent-Length: {}\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+
+    /// an empty cache directory unique to a test
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("advent_fetch_{}_{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+
+        dir
+    }
+
+    #[test]
+    fn fetch_and_cache() {
+        let (url, requests) = serve(vec![(200, "1abc2\n")]);
+        let dir = cache_dir("cache");
+        let mut fetcher = Fetcher::new(Http, "cookie", &dir).with_base_url(url);
+
+        let msg = "should download the input";
+        let actual = fetcher.fetch(2023, 1).unwrap();
+        assert_eq!(actual, "1abc2\n", "{}", msg);
+
+        let msg = "should request the day's input with the session cookie";
+        let head = requests.recv().unwrap();
+        assert!(
+            head.starts_with("GET /2023/day/1/input HTTP/1.1"),
+            "{}",
+            msg
+        );
+        assert!(head.contains("Cookie: session=cookie"), "{}", msg);
+
+        let msg = "should cache the input in the input directory layout";
+        assert!(dir.join("2023").join("01-1.txt").is_file(), "{}", msg);
+
+        let msg = "should read cached inputs without another request";
+        let actual = fetcher.fetch(2023, 1).unwrap();
+        assert_eq!(actual, "1abc2\n", "{}", msg);
+        assert!(requests.try_recv().is_err(), "{}", msg);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn fetch_errors() {
+        let (url, _requests) = serve(vec![(400, LOGIN_PAGE), (404, "Not unlocked yet\n")]);
+        let dir = cache_dir("errors");
+        let mut fetcher = Fetcher::new(Http, "expired", &dir)
+            .with_base_url(url)
+            .with_min_interval(Duration::ZERO);
+
+        let msg = "should detect the log in page";
+        let actual = fetcher.fetch(2023, 2).unwrap_err();
+        assert!(actual.reason.contains("session cookie rejected"), "{}", msg);
+        assert!(!fetcher.is_cached(2023, 2), "{}", msg);
+
+        let msg = "should report unsuccessful requests";
+        let actual = fetcher.fetch(2023, 3).unwrap_err();
+        assert!(actual.reason.contains("status 404"), "{}", msg);
+        assert!(!fetcher.is_cached(2023, 3), "{}", msg);
+
+        let msg = "should reject days without a puzzle";
+        assert!(fetcher.fetch(2023, 26).is_err(), "{}", msg);
+        assert!(fetcher.fetch(2014, 1).is_err(), "{}", msg);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn rate_limit() {
+        let (url, _requests) = serve(vec![(200, "a\n"), (200, "b\n"), (200, "c\n")]);
+        let dir = cache_dir("rate");
+        let fetcher = || {
+            Fetcher::new(Http, "cookie", &dir)
+                .with_base_url(url.clone())
+                .with_min_interval(Duration::from_millis(100))
+        };
+
+        let msg = "should wait the minimum interval between requests";
+        let mut first = fetcher();
+        let start = Instant::now();
+        first.fetch(2022, 1).unwrap();
+        first.fetch(2022, 2).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100), "{}", msg);
+
+        let msg = "should wait the minimum interval after another fetcher's request";
+        let start = Instant::now();
+        fetcher().fetch(2022, 3).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(100), "{}", msg);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
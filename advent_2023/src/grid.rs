@@ -0,0 +1,241 @@
+//! Rectangular 2d grid addressed by [`Coord`]
+use std::{fmt, ops::Index};
+
+use crate::{Coord, Error};
+
+/// Rectangular grid of cells, stored row by row
+///
+/// Cells are addressed by `Coord(row, column)`, with `Coord(0, 0)` at the top left.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// create a grid from its cells in row order, failing if they don't fill `width` columns
+    /// exactly
+    pub fn new(width: usize, cells: Vec<T>) -> Result<Self, Error> {
+        if !cells.len().is_multiple_of(width) {
+            return Err(Error::new(format!(
+                "{} cells don't fill rows of width {}",
+                cells.len(),
+                width
+            )));
+        }
+        let height = cells.len().checked_div(width).unwrap_or_default();
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// parse a grid from lines of text, converting each char with `parse`
+    ///
+    /// Every line must be the same length
+    pub fn parse_with<E>(text: &str, parse: impl Fn(char) -> Result<T, E>) -> Result<Self, Error>
+    where
+        E: fmt::Display,
+    {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(text.len());
+        for (idx, line) in text.lines().enumerate() {
+            let line_err = |reason: String| Error::at_line(idx + 1, reason);
+            let len = line.chars().count();
+            match width {
+                Some(width) if width != len => {
+                    return Err(line_err(format!(
+                        "Expected a row of width {}, found {}",
+                        width, len
+                    )));
+                }
+                _ => width = Some(len),
+            }
+
+            for ch in line.chars() {
+                cells.push(parse(ch).map_err(|err| line_err(err.to_string()))?);
+            }
+        }
+
+        Self::new(width.unwrap_or_default(), cells)
+    }
+
+    /// number of columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// number of rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// whether `coord` lies within the grid
+    pub fn contains(&self, coord: Coord) -> bool {
+        self.index_of(coord).is_some()
+    }
+
+    /// the cell at `coord`, or `None` if it lies outside the grid
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.index_of(coord).map(|idx| &self.cells[idx])
+    }
+
+    /// the mutable cell at `coord`, or `None` if it lies outside the grid
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.index_of(coord).map(|idx| &mut self.cells[idx])
+    }
+
+    /// the cells of a row, or `None` if it lies outside the grid
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        self.rows().nth(row)
+    }
+
+    /// every row, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a 0 size
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// the cells of a column from top to bottom, empty if it lies outside the grid
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        let start = if column < self.width {
+            column
+        } else {
+            self.cells.len()
+        };
+        self.cells.iter().skip(start).step_by(self.width.max(1))
+    }
+
+    /// every column, from left to right
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    /// the orthogonally adjacent coordinates of `coord` that lie within the grid
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        coord
+            .neighbors4()
+            .filter(|neighbor| self.contains(*neighbor))
+    }
+
+    /// the orthogonally and diagonally adjacent coordinates of `coord` that lie within the grid
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        coord
+            .neighbors8()
+            .filter(|neighbor| self.contains(*neighbor))
+    }
+
+    /// every cell and its coordinate, in row order
+    pub fn iter_coords(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells.iter().enumerate().map(|(idx, cell)| {
+            let coord = Coord::from((idx / self.width, idx % self.width));
+            (coord, cell)
+        })
+    }
+
+    /// index into `cells` of an in bounds coordinate
+    fn index_of(&self, coord: Coord) -> Option<usize> {
+        let (row, column) = <(usize, usize)>::try_from(coord).ok()?;
+
+        (row < self.height && column < self.width).then_some(row * self.width + column)
+    }
+}
+
+impl<T: From<char>> Grid<T> {
+    /// parse a grid from lines of text, one cell per char
+    ///
+    /// Every line must be the same length
+    pub fn from_text(text: &str) -> Result<Self, Error> {
+        Self::parse_with(text, |ch| Ok::<_, Error>(T::from(ch)))
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &Self::Output {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", coord))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TEXT: &str = "abc\ndef\n";
+
+    #[test]
+    fn from_text() {
+        let msg = "should parse one cell per char";
+        let grid = Grid::<char>::from_text(TEXT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2), "{}", msg);
+        assert_eq!(grid.to_string(), TEXT, "{}", msg);
+
+        let msg = "should report the line of a ragged row";
+        let expected = Some(2);
+        let actual = Grid::<char>::from_text("abc\nde").unwrap_err().line;
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should report the line of an invalid cell";
+        let expected = Some(2);
+        let actual = Grid::parse_with("12\n3x", |ch| ch.to_digit(10).ok_or("not a digit"))
+            .unwrap_err()
+            .line;
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn get() {
+        let msg = "should bounds check coordinates";
+        let grid = Grid::<char>::from_text(TEXT).unwrap();
+        assert_eq!(grid.get(Coord(1, 2)), Some(&'f'), "{}", msg);
+        assert_eq!(grid[Coord(0, 1)], 'b', "{}", msg);
+        assert_eq!(grid.get(Coord(2, 0)), None, "{}", msg);
+        assert_eq!(grid.get(Coord(0, 3)), None, "{}", msg);
+        assert_eq!(grid.get(Coord(-1, 0)), None, "{}", msg);
+    }
+
+    #[test]
+    fn iterators() {
+        let grid = Grid::<char>::from_text(TEXT).unwrap();
+
+        let msg = "should iterate rows from top to bottom";
+        let expected = vec!["abc".to_string(), "def".to_string()];
+        let actual: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should iterate columns from left to right";
+        let expected = vec!["ad".to_string(), "be".to_string(), "cf".to_string()];
+        let actual: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(actual, expected, "{}", msg);
+        assert_eq!(grid.column(3).count(), 0, "{}", msg);
+
+        let msg = "should pair each cell with its coordinate";
+        let expected = Some((Coord(1, 0), &'d'));
+        let actual = grid.iter_coords().nth(3);
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should only list neighbors within the grid";
+        let expected = vec![Coord(0, 1), Coord(1, 1), Coord(1, 0)];
+        let actual: Vec<_> = grid.neighbors8(Coord(0, 0)).collect();
+        assert_eq!(actual, expected, "{}", msg);
+        assert_eq!(grid.neighbors4(Coord(1, 2)).count(), 2, "{}", msg);
+    }
+}
@@ -1,114 +1,325 @@
 #![deny(missing_debug_implementations)]
 #![deny(missing_docs)]
 //! Advent of Code 2023 Solutions
-use std::ops::{Add, Sub};
-use std::{fmt, fs, ops::RangeInclusive, path::Path};
+use std::{collections::BTreeMap, fmt, fs, ops::RangeInclusive, path::Path, time::Duration};
 
-use parser::three::lib::{any_of, choice, keep_first, p_char, p_int, spaces};
+use parser::three::lib::{
+    any_of, choice, keep_first, keep_second, opt, p_char, p_int, p_string, sep_by1, spaces, Parser,
+};
 
+mod coord;
+mod error;
+mod grid;
+
+pub mod answers;
+pub mod bench;
 pub mod day_01;
 pub mod day_02;
 pub mod day_03;
 pub mod day_04;
+pub mod fetch;
+pub mod memory;
+pub mod parse;
+pub mod pool;
+pub mod range_set;
+pub mod registry;
+pub mod report;
+pub mod search;
+pub mod watchdog;
+
+pub use coord::{Coord, Coord3};
+pub use error::{Error, ErrorKind, ParseError};
+pub use grid::Grid;
+
+/// Event year of the solutions in this crate
+pub const YEAR: usize = 2023;
+
+/// Earliest Advent of Code event year
+pub const FIRST_YEAR: usize = 2015;
 
 /// read the specified file at `file_path` into a `String`
-///
-/// Panic! on error
-pub fn read_file(file_path: &str) -> String {
+pub fn read_file(file_path: &str) -> Result<String, Error> {
     let path = Path::new(file_path);
     let display = path.display();
 
-    fs::read_to_string(path).unwrap_or_else(|why| panic!("couldnt open {}: {}", display, why))
+    fs::read_to_string(path).map_err(|why| Error::new(format!("couldnt open {}: {}", display, why)))
 }
 
-/// deserializes a 2d vec of [`T`] from the specified file path
-pub fn load_terrain<T>(file_path: &str) -> Vec<Vec<T>>
+/// deserializes a [`Grid`] of [`T`] from the specified file path
+pub fn load_terrain<T>(file_path: &str) -> Result<Grid<T>, Error>
 where
     T: From<char>,
 {
-    read_file(file_path)
-        .lines()
-        .map(|line| line.chars().map(From::from).collect::<Vec<_>>())
-        .collect()
+    Grid::from_text(&read_file(file_path)?)
 }
 
-/// Grid coordinate
-#[derive(Clone, Copy, Default, Debug, Eq, Hash, PartialEq)]
-pub struct Coord(pub isize, pub isize);
-
-impl Add for Coord {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        Self(self.0 + other.0, self.1 + other.1)
-    }
+/// apply a fallible `parse` fn to each line of `input`, tagging failures with their line number
+pub fn parse_lines<'a, T, E, F>(
+    input: &'a str,
+    parse: F,
+) -> impl Iterator<Item = Result<T, Error>> + 'a
+where
+    E: fmt::Display,
+    F: Fn(&'a str) -> Result<T, E> + 'a,
+{
+    input.lines().enumerate().map(move |(idx, line)| {
+        parse(line).map_err(|reason| Error::at_line(idx + 1, reason.to_string()))
+    })
 }
 
-impl Sub for Coord {
-    type Output = Self;
-
-    fn sub(self, other: Self) -> Self {
-        Self(self.0 - other.0, self.1 - other.1)
-    }
-}
+/// AoC problem solver function pointer, taking the puzzle input text
+pub type Solver<T> = fn(&str) -> Result<T, Error>;
 
-impl From<(isize, isize)> for Coord {
-    fn from(value: (isize, isize)) -> Self {
-        Coord(value.0, value.1)
-    }
+/// Run time of each phase of a solver invocation
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PhaseTimes {
+    /// Input parsing time
+    pub parse: Duration,
+    /// Solving time, given the parsed input
+    pub solve: Duration,
 }
 
-/// AoC problem solver function pointer
-pub type Solver<T> = fn(&str) -> T;
+/// AoC problem solver function pointer which times its parsing and solving phases separately
+pub type PhasedSolver<T> = fn(&str) -> Result<(T, PhaseTimes), Error>;
 
 /// AoC problem solution
 pub struct Solution<T: fmt::Display> {
+    /// Event year
+    pub year: usize,
+    /// Puzzle day number
+    pub day: usize,
+    /// Puzzle title
+    pub title: &'static str,
     /// Input filename
     pub input: &'static str,
     /// Part one output label and solving fn
     pub one: Option<(&'static str, Solver<T>)>,
     /// Part two output label and solving fn
     pub two: Option<(&'static str, Solver<T>)>,
+    /// Part one phase timing solver, if the day exposes its parser
+    pub phased_one: Option<PhasedSolver<T>>,
+    /// Part two phase timing solver, if the day exposes its parser
+    pub phased_two: Option<PhasedSolver<T>>,
 }
 
 impl<T: fmt::Display> std::fmt::Debug for Solution<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Solution")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .field("title", &self.title)
             .field("input file", &self.input)
             .finish()
     }
 }
 
+/// A puzzle day and its solvers, as enumerated by the [`registry`]
+pub trait Day: Sync {
+    /// Event year
+    fn year(&self) -> usize;
+    /// Puzzle day number
+    fn number(&self) -> usize;
+    /// Puzzle title
+    fn title(&self) -> &'static str;
+    /// Input filename
+    fn input(&self) -> &'static str;
+    /// Part one output label and solving fn
+    fn one(&self) -> Option<(&'static str, Solver<String>)>;
+    /// Part two output label and solving fn
+    fn two(&self) -> Option<(&'static str, Solver<String>)>;
+
+    /// Part one phase timing solver, if the day exposes its parser
+    fn phased_one(&self) -> Option<PhasedSolver<String>> {
+        None
+    }
+
+    /// Part two phase timing solver, if the day exposes its parser
+    fn phased_two(&self) -> Option<PhasedSolver<String>> {
+        None
+    }
+}
+
+impl Day for Solution<String> {
+    fn year(&self) -> usize {
+        self.year
+    }
+
+    fn number(&self) -> usize {
+        self.day
+    }
+
+    fn title(&self) -> &'static str {
+        self.title
+    }
+
+    fn input(&self) -> &'static str {
+        self.input
+    }
+
+    fn one(&self) -> Option<(&'static str, Solver<String>)> {
+        self.one
+    }
+
+    fn two(&self) -> Option<(&'static str, Solver<String>)> {
+        self.two
+    }
+
+    fn phased_one(&self) -> Option<PhasedSolver<String>> {
+        self.phased_one
+    }
+
+    fn phased_two(&self) -> Option<PhasedSolver<String>> {
+        self.phased_two
+    }
+}
+
+impl fmt::Debug for dyn Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Day")
+            .field("year", &self.year())
+            .field("number", &self.number())
+            .field("title", &self.title())
+            .finish()
+    }
+}
+
 /// format as [`Solver`] with the supplied label and solving fn
+///
+/// With a parsing fn, the solving fn takes the parsed input instead of the input text
 #[macro_export]
 macro_rules! to_solver {
     ($label: literal, $fn: path) => {
-        Some(($label, |input| $fn(input).to_string()))
+        Some(($label, |input| $fn(input).map(|result| result.to_string())))
     };
     ($label: expr, $fn: path) => {
-        Some(($label, |input| $fn(input).to_string()))
+        Some(($label, |input| $fn(input).map(|result| result.to_string())))
+    };
+    ($label: literal, $parse: path, $fn: path) => {
+        Some(($label, |input| {
+            $parse(input)
+                .map(|parsed| $fn(parsed).to_string())
+                .map_err($crate::Error::from)
+        }))
     };
 }
 
-/// format as [`Solution`]
+/// format as [`PhasedSolver`] with the supplied parsing fn and solving fn
+#[macro_export]
+macro_rules! to_phased {
+    ($parse: path, $fn: path) => {
+        Some(|input| {
+            let start = std::time::Instant::now();
+            let parsed = $parse(input)?;
+            let parse = start.elapsed();
+
+            let start = std::time::Instant::now();
+            let result = $fn(parsed);
+            let solve = start.elapsed();
+
+            Ok((result.to_string(), $crate::PhaseTimes { parse, solve }))
+        })
+    };
+}
+
+/// format as [`Solution`] for the supplied day number, title and input filename
+///
+/// Supplying the day's parsing fn after the input filename enables phase timing, with each
+/// solving fn taking the parsed input. Solutions are for the crate's [`YEAR`]; override the
+/// `year` field with struct update syntax for other events.
 #[macro_export]
 macro_rules! to_solution {
-    ($file: literal, ($fn_one: path,$text_one: literal), ($fn_two: path,$text_two: literal)) => {
-        Solution {
+    ($day: literal, $title: literal, $file: literal, ($fn_one: path,$text_one: literal), ($fn_two: path,$text_two: literal)) => {
+        $crate::Solution {
+            year: $crate::YEAR,
+            day: $day,
+            title: $title,
+            input: $file,
+            one: $crate::to_solver!($text_one, $fn_one),
+            two: $crate::to_solver!($text_two, $fn_two),
+            phased_one: None,
+            phased_two: None,
+        }
+    };
+    ($day: literal, $title: literal, $file: literal, ($fn_one: path,$text_one: literal)) => {
+        $crate::Solution {
+            year: $crate::YEAR,
+            day: $day,
+            title: $title,
+            input: $file,
+            one: $crate::to_solver!($text_one, $fn_one),
+            two: None,
+            phased_one: None,
+            phased_two: None,
+        }
+    };
+    ($day: literal, $title: literal, $file: literal, $parse: path, ($fn_one: path,$text_one: literal), ($fn_two: path,$text_two: literal)) => {
+        $crate::Solution {
+            year: $crate::YEAR,
+            day: $day,
+            title: $title,
             input: $file,
-            one: to_solver!($text_one, $fn_one),
-            two: to_solver!($text_two, $fn_two),
+            one: $crate::to_solver!($text_one, $parse, $fn_one),
+            two: $crate::to_solver!($text_two, $parse, $fn_two),
+            phased_one: $crate::to_phased!($parse, $fn_one),
+            phased_two: $crate::to_phased!($parse, $fn_two),
         }
     };
-    ($file: literal, ($fn_one: path,$text_one: literal)) => {
-        Solution {
+    ($day: literal, $title: literal, $file: literal, $parse: path, ($fn_one: path,$text_one: literal)) => {
+        $crate::Solution {
+            year: $crate::YEAR,
+            day: $day,
+            title: $title,
             input: $file,
-            one: to_solver!($text_one, $fn_one),
+            one: $crate::to_solver!($text_one, $parse, $fn_one),
             two: None,
+            phased_one: $crate::to_phased!($parse, $fn_one),
+            phased_two: None,
         }
     };
 }
 
+/// Parts of a puzzle day selected to run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Parts {
+    /// Whether part one is selected
+    pub one: bool,
+    /// Whether part two is selected
+    pub two: bool,
+}
+
+impl Parts {
+    /// Both parts of a day
+    pub const BOTH: Parts = Parts {
+        one: true,
+        two: true,
+    };
+
+    /// the selection of a single 1-based part
+    pub fn only(part: usize) -> Self {
+        Self {
+            one: part == 1,
+            two: part == 2,
+        }
+    }
+
+    /// whether the 1-based `part` is selected
+    pub fn contains(&self, part: usize) -> bool {
+        match part {
+            1 => self.one,
+            2 => self.two,
+            _ => false,
+        }
+    }
+
+    /// parts selected by either selection
+    pub fn union(self, other: Parts) -> Self {
+        Self {
+            one: self.one || other.one,
+            two: self.two || other.two,
+        }
+    }
+}
+
 /// user controlled operation
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Command {
@@ -120,6 +331,24 @@ pub enum Command {
     Day(usize),
     /// Run solutions for a range of days
     Range(RangeInclusive<usize>),
+    /// Run a single part of a day, as `(day, part)`
+    Part(usize, usize),
+    /// Run every part from one `(day, part)` through another, eg `4.1-6.2`
+    PartRange((usize, usize), (usize, usize)),
+    /// Run each comma separated day selection, eg `1,3,5-7`
+    List(Vec<Command>),
+    /// Run the day selection of the inner command for a specific event year
+    Year(usize, Box<Command>),
+    /// Benchmark the solutions selected by the inner command
+    Bench(Box<Command>),
+    /// Verify the solutions selected by the inner command against the expected answers
+    Verify(Box<Command>),
+    /// Show the interactive command help
+    Help,
+    /// List the registered days and their available inputs
+    ListDays,
+    /// Repeat the previous command
+    Repeat,
 }
 
 // parse a command from user input
@@ -127,23 +356,216 @@ impl std::str::FromStr for Command {
     type Err = String;
 
     fn from_str(input: &str) -> Result<Command, Self::Err> {
-        let dash = spaces().and_then(p_char('-')).and_then(spaces());
-        let range = keep_first(p_int(10), dash.clone())
-            .and_then(p_int(10))
-            .map(|(a, b)| Command::Range(a as usize..=b as usize));
-        let day = p_int(10).map(|d| Command::Day(d as usize));
-        let quit = any_of(['q', 'Q']).and_then(spaces()).map(|_| Command::Quit);
-        let all = any_of(['a', 'A']).and_then(spaces()).map(|_| Command::All);
-
-        let p_command = choice([range, day, all, quit]);
-
-        match p_command.parse(input) {
-            Ok((_, command)) => Ok(command),
+        let quit = p_keyword("quit").map(|_| Command::Quit);
+        let all = p_keyword("all").map(|_| Command::All);
+        let help = choice([p_keyword("help"), p_char('?').map(|_| ())]).map(|_| Command::Help);
+        let list = p_keyword("list").map(|_| Command::ListDays);
+        let repeat = p_string("!!").map(|_| Command::Repeat);
+        let selection = p_selection();
+        let days = choice([selection.clone(), all.clone()]);
+        let year = keep_first(p_int(10), p_char(' ').and_then(spaces()))
+            .and_then(days)
+            .map(|(year, days)| Command::Year(year as usize, Box::new(days)));
+        let year_days = choice([year.clone(), selection.clone(), all.clone()]);
+        let bench = p_keyword("bench")
+            .and_then(spaces())
+            .and_then(year_days.clone())
+            .map(|(_, days)| Command::Bench(Box::new(days)));
+        let verify = p_keyword("verify")
+            .and_then(spaces())
+            .and_then(year_days)
+            .map(|(_, days)| Command::Verify(Box::new(days)));
+
+        let p_command = choice([
+            bench, verify, year, selection, all, help, list, repeat, quit,
+        ]);
+
+        match p_command.parse(input.trim()) {
+            Ok((rest, command)) if rest.trim().is_empty() => command.validate(),
+            Ok((rest, _)) => Err(format!("Unexpected '{}'", rest.trim())),
             Err(err) => Err(err.to_string()),
         }
     }
 }
 
+/// parse a case insensitive keyword or any abbreviation of it, eg `b`, `ben` or `BENCH`
+fn p_keyword(word: &'static str) -> Parser<()> {
+    let p_letter = |letter: char| {
+        any_of([letter.to_ascii_lowercase(), letter.to_ascii_uppercase()]).map(|_| ())
+    };
+    let mut letters = word.chars().rev();
+    let last = p_letter(letters.next().expect("keyword should not be empty"));
+
+    // each letter after the first is only matched following the letter before it
+    letters.fold(last, |rest, letter| keep_first(p_letter(letter), opt(rest)))
+}
+
+/// parse a day, optionally with a part, eg `3` or `3.2`
+fn p_day_part() -> Parser<(usize, Option<usize>)> {
+    p_int(10)
+        .and_then(opt(keep_second(p_char('.'), p_int(10))))
+        .map(|(day, part)| (day as usize, part.map(|part| part as usize)))
+}
+
+/// parse a day or part selection, eg `3`, `3.2`, `2-10` or `4.1-6.2`
+fn p_day_selection() -> Parser<Command> {
+    let dash = spaces().and_then(p_char('-')).and_then(spaces());
+
+    p_day_part()
+        .and_then(opt(keep_second(dash, p_day_part())))
+        .map(|selection| match selection {
+            ((day, None), None) => Command::Day(day),
+            ((day, Some(part)), None) => Command::Part(day, part),
+            ((start, None), Some((end, None))) => Command::Range(start..=end),
+            ((start, start_part), Some((end, end_part))) => Command::PartRange(
+                (start, start_part.unwrap_or(1)),
+                (end, end_part.unwrap_or(2)),
+            ),
+        })
+}
+
+/// parse a comma separated list of day or part selections, eg `1,3,5-7`
+fn p_selection() -> Parser<Command> {
+    let comma = spaces().and_then(p_char(',')).and_then(spaces());
+
+    sep_by1(p_day_selection(), comma).map(|mut list| {
+        if list.len() == 1 {
+            list.remove(0)
+        } else {
+            Command::List(list)
+        }
+    })
+}
+
+impl Command {
+    /// parse a command from command line arguments, eg `3`, `2-10`, `1,3.2`, `--all`,
+    /// `--bench 3` or `--verify --all`
+    pub fn from_args<I, S>(args: I) -> Result<Command, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let args: Vec<String> = args
+            .into_iter()
+            .map(|arg| arg.as_ref().to_owned())
+            .collect();
+
+        match args.as_slice() {
+            [] => Err("No command supplied".to_string()),
+            [flag] if flag == "--all" || flag == "-a" => Ok(Command::All),
+            [flag] if flag == "--list" || flag == "-l" => Ok(Command::ListDays),
+            [flag, days @ ..] if flag == "--bench" || flag == "-b" => {
+                Ok(Command::Bench(Box::new(Command::days_from_args(days)?)))
+            }
+            [flag, days @ ..] if flag == "--verify" || flag == "-v" => {
+                Ok(Command::Verify(Box::new(Command::days_from_args(days)?)))
+            }
+            [year, days @ ..] if !days.is_empty() && year.parse::<usize>().is_ok() => {
+                match Command::from_args(days) {
+                    Ok(days) if days.is_days() => {
+                        Command::Year(year.parse().unwrap_or_default(), Box::new(days)).validate()
+                    }
+                    _ => args.join(" ").parse(),
+                }
+            }
+            _ => args.join(" ").parse(),
+        }
+    }
+
+    /// parse a day selection (all days, a single day or a range, optionally for a specific
+    /// year) from command line arguments
+    fn days_from_args(args: &[String]) -> Result<Command, String> {
+        match Command::from_args(args)? {
+            days if days.is_days() || matches!(days, Command::Year(..)) => Ok(days),
+            _ => Err(format!(
+                "Expected a day selection, found '{}'",
+                args.join(" ")
+            )),
+        }
+    }
+
+    /// whether the command is a day selection without an event year
+    fn is_days(&self) -> bool {
+        matches!(
+            self,
+            Command::All
+                | Command::Day(_)
+                | Command::Range(_)
+                | Command::Part(..)
+                | Command::PartRange(..)
+                | Command::List(_)
+        )
+    }
+
+    /// reject event years before the first Advent of Code and parts other than one and two
+    fn validate(self) -> Result<Command, String> {
+        let invalid_part = |part: &usize| !(1..=2).contains(part);
+        match &self {
+            Command::Year(year, _) if *year < FIRST_YEAR => {
+                Err(format!("Invalid event year {}", year))
+            }
+            Command::Part(_, part) | Command::PartRange((_, part), _) if invalid_part(part) => {
+                Err(format!("Invalid part {}", part))
+            }
+            Command::PartRange(_, (_, part)) if invalid_part(part) => {
+                Err(format!("Invalid part {}", part))
+            }
+            Command::Year(_, days) | Command::Bench(days) | Command::Verify(days) => {
+                days.as_ref().clone().validate().map(|_| self)
+            }
+            Command::List(list) => list
+                .iter()
+                .try_for_each(|days| days.clone().validate().map(|_| ()))
+                .map(|_| self),
+            _ => Ok(self),
+        }
+    }
+
+    /// the days and parts selected by a day selection, in day order, or `None` for commands
+    /// which aren't a day selection
+    ///
+    /// `all` lists the days selected by [`Command::All`]
+    pub fn day_parts(&self, all: &[usize]) -> Option<Vec<(usize, Parts)>> {
+        let mut selected = BTreeMap::new();
+        let mut select = |day: usize, parts: Parts| {
+            selected
+                .entry(day)
+                .and_modify(|selected: &mut Parts| *selected = selected.union(parts))
+                .or_insert(parts);
+        };
+
+        match self {
+            Command::All => all.iter().for_each(|day| select(*day, Parts::BOTH)),
+            Command::Day(day) => select(*day, Parts::BOTH),
+            Command::Range(range) => range.clone().for_each(|day| select(day, Parts::BOTH)),
+            Command::Part(day, part) => select(*day, Parts::only(*part)),
+            Command::PartRange((start, start_part), (end, end_part)) => {
+                for day in *start..=*end {
+                    let first = if day == *start { *start_part } else { 1 };
+                    let last = if day == *end { *end_part } else { 2 };
+                    let parts = Parts {
+                        one: first <= 1 && 1 <= last,
+                        two: first <= 2 && 2 <= last,
+                    };
+                    if parts.one || parts.two {
+                        select(day, parts);
+                    }
+                }
+            }
+            Command::List(list) => {
+                for days in list {
+                    for (day, parts) in days.day_parts(all)? {
+                        select(day, parts);
+                    }
+                }
+            }
+            _ => return None,
+        }
+
+        Some(selected.into_iter().collect())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -175,7 +597,168 @@ mod test {
         let actual: Command = "10".parse().unwrap();
         assert_eq!(actual, expected, "{}", msg);
 
+        let expected = Command::Bench(Box::new(Command::Range(2..=4)));
+        let actual: Command = "b 2-4".parse().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Bench(Box::new(Command::All));
+        let actual: Command = "B a".parse().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Verify(Box::new(Command::Day(3)));
+        let actual: Command = "v 3".parse().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Year(2022, Box::new(Command::Range(1..=25)));
+        let actual: Command = "2022 1-25".parse().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Bench(Box::new(Command::Year(2023, Box::new(Command::Day(5)))));
+        let actual: Command = "b 2023 5".parse().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let actual = "10 25".parse::<Command>().is_err();
+        assert!(actual, "{}", msg);
+
+        let msg = "should parse day lists and part selections";
+        let expected = Command::List(vec![
+            Command::Day(1),
+            Command::Day(3),
+            Command::Range(5..=7),
+        ]);
+        let actual: Command = "1,3, 5-7".parse().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Part(3, 2);
+        let actual: Command = "3.2".parse().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::PartRange((4, 1), (6, 2));
+        let actual: Command = "4.1-6.2".parse().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::PartRange((4, 2), (6, 2));
+        let actual: Command = "4.2-6".parse().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Verify(Box::new(Command::Year(
+            2022,
+            Box::new(Command::List(vec![Command::Part(1, 1), Command::Day(2)])),
+        )));
+        let actual: Command = "v 2022 1.1,2".parse().unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let actual = "3.3".parse::<Command>().is_err();
+        assert!(actual, "{}", msg);
+
+        let actual = "1,2.0".parse::<Command>().is_err();
+        assert!(actual, "{}", msg);
+
         let actual = "foo".parse::<Command>().is_err();
         assert!(actual, "{}", msg);
+
+        let msg = "should parse named commands and their abbreviations in any case";
+        for (input, expected) in [
+            ("help", Command::Help),
+            ("H", Command::Help),
+            ("?", Command::Help),
+            ("list", Command::ListDays),
+            ("Li", Command::ListDays),
+            ("!!", Command::Repeat),
+            ("quit", Command::Quit),
+            ("ALL", Command::All),
+            ("bench 3", Command::Bench(Box::new(Command::Day(3)))),
+            ("Ver 1-2", Command::Verify(Box::new(Command::Range(1..=2)))),
+        ] {
+            let actual: Command = input.parse().unwrap();
+            assert_eq!(actual, expected, "{} '{}'", msg, input);
+        }
+
+        let msg = "should reject input after a command";
+        for input in ["lister", "helpme", "q 3", "alla", "3 foo"] {
+            let actual = input.parse::<Command>().is_err();
+            assert!(actual, "{} '{}'", msg, input);
+        }
+    }
+
+    #[test]
+    fn command_from_args() {
+        let msg = "should parse command line arguments into a command";
+        let expected = Command::All;
+        let actual = Command::from_args(["--all"]).unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Day(3);
+        let actual = Command::from_args(["3"]).unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Range(2..=10);
+        let actual = Command::from_args(["2-10"]).unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Range(2..=10);
+        let actual = Command::from_args(["2", "-", "10"]).unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Bench(Box::new(Command::All));
+        let actual = Command::from_args(["--bench", "--all"]).unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let actual = Command::from_args(["--bench", "q"]).is_err();
+        assert!(actual, "{}", msg);
+
+        let expected = Command::Verify(Box::new(Command::Range(1..=4)));
+        let actual = Command::from_args(["--verify", "1-4"]).unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Year(2022, Box::new(Command::All));
+        let actual = Command::from_args(["2022", "--all"]).unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Year(2023, Box::new(Command::Day(5)));
+        let actual = Command::from_args(["2023", "5"]).unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Year(2023, Box::new(Command::Part(5, 2)));
+        let actual = Command::from_args(["2023", "5.2"]).unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let expected = Command::Bench(Box::new(Command::List(vec![
+            Command::Day(1),
+            Command::Day(3),
+        ])));
+        let actual = Command::from_args(["--bench", "1,3"]).unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let actual = Command::from_args(Vec::<String>::new()).is_err();
+        assert!(actual, "{}", msg);
+    }
+
+    #[test]
+    fn day_parts() {
+        let one = Parts::only(1);
+        let two = Parts::only(2);
+
+        let msg = "should select both parts of every listed day, in day order";
+        let expected = vec![(1, Parts::BOTH), (3, Parts::BOTH), (5, Parts::BOTH)];
+        let actual: Command = "5,1,3".parse().unwrap();
+        assert_eq!(actual.day_parts(&[]), Some(expected), "{}", msg);
+
+        let msg = "should select the parts between the ends of a part range";
+        let expected = vec![(4, two), (5, Parts::BOTH), (6, one)];
+        let actual: Command = "4.2-6.1".parse().unwrap();
+        assert_eq!(actual.day_parts(&[]), Some(expected), "{}", msg);
+
+        let msg = "should merge the parts selected for a day";
+        let expected = vec![(3, Parts::BOTH), (4, one)];
+        let actual: Command = "3.2,4.1,3.1".parse().unwrap();
+        assert_eq!(actual.day_parts(&[]), Some(expected), "{}", msg);
+
+        let msg = "should select all the listed days";
+        let expected = vec![(2, Parts::BOTH), (4, Parts::BOTH)];
+        assert_eq!(Command::All.day_parts(&[2, 4]), Some(expected), "{}", msg);
+
+        let msg = "should only select days from day selections";
+        assert_eq!(Command::Quit.day_parts(&[1]), None, "{}", msg);
     }
 }
@@ -1,11 +1,19 @@
 use std::{
-    io::{self, Write},
-    ops::RangeInclusive,
+    env, fs,
+    io::{self, Read, Write},
+    ops::ControlFlow,
     path::PathBuf,
+    process,
+    sync::{Arc, OnceLock},
     time::{Duration, Instant},
 };
 
-use advent_2023::*;
+use advent_2023::{
+    answers::Answers,
+    report::{Format, Record},
+    watchdog::Outcome,
+    *,
+};
 
 #[cfg(debug_assertions)]
 fn get_root_dir() -> PathBuf {
@@ -22,127 +30,950 @@ pub fn get_root_dir() -> PathBuf {
     }
 }
 
-/// run a problem solver and return its output and run time
-fn solve_day<T>(file: &'static str) -> impl Fn(Solver<T>) -> (T, Duration) {
-    move |f| {
-        let input_path = format!("{}/input/{}.txt", get_root_dir().display(), file);
-        let start = Instant::now();
-        let result = f(&input_path);
-        let dur = start.elapsed();
-
-        (result, dur)
-    }
-}
-
-static SOLUTIONS: &[Option<Solution<String>>] = &[
-    Some(to_solution!(
-        "01-1",
-        (day_01::one, "Calibration values"),
-        (day_01::two, "Calibration values redux")
-    )),
-    Some(to_solution!(
-        "02-1",
-        (day_02::one, "Possible games"),
-        (day_02::two, "Power of cubes")
-    )),
-    Some(to_solution!(
-        "03-1",
-        (day_03::one, "Part number search"),
-        (day_03::two, "Gear ratio sum")
-    )),
-    Some(to_solution!(
-        "04-1",
-        (day_04::one, "Scratch card points"),
-        (day_04::two, "Card count")
-    )),
-];
-
-/// run a single day
+/// environment variable overriding the puzzle input directory
+const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// the input directory of an event year, under `AOC_INPUT_DIR` if set or `input` in the root dir
+fn input_dir(year: usize) -> PathBuf {
+    env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| get_root_dir().join("input"))
+        .join(year.to_string())
+}
+
+/// read a puzzle input file, with a hint on how to override the path when it is missing
+fn load_input_file(input_path: &str) -> Result<String, Error> {
+    if !PathBuf::from(input_path).is_file() {
+        return Err(Error::new(format!(
+            "input file {} not found (pass --input <path> or set {})",
+            input_path, INPUT_DIR_VAR
+        )));
+    }
+    read_file(input_path)
+}
+
+/// standard input, once read by [`InputSource::load`]
+static STDIN: OnceLock<Result<String, Error>> = OnceLock::new();
+
+/// Where puzzle input is read from
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+enum InputSource {
+    /// the day's input file in the event year's input directory
+    #[default]
+    Directory,
+    /// an explicit file
+    File(String),
+    /// standard input
+    Stdin,
+}
+
+impl InputSource {
+    /// name of the input for a problem, for error messages
+    fn name(&self, year: usize, file: &'static str) -> String {
+        match self {
+            InputSource::Directory => input_dir(year)
+                .join(format!("{}.txt", file))
+                .display()
+                .to_string(),
+            InputSource::File(input_path) => input_path.clone(),
+            InputSource::Stdin => "<stdin>".to_string(),
+        }
+    }
+
+    /// read the input for a problem
+    ///
+    /// Standard input is read in full on first use and cached for every later problem.
+    fn load(&self, year: usize, file: &'static str) -> Result<String, Error> {
+        match self {
+            InputSource::Directory => {
+                let input_path = input_dir(year).join(format!("{}.txt", file));
+                load_input_file(&input_path.display().to_string())
+            }
+            InputSource::File(input_path) => load_input_file(input_path),
+            InputSource::Stdin => STDIN
+                .get_or_init(|| {
+                    let mut input = String::new();
+                    io::stdin()
+                        .read_to_string(&mut input)
+                        .map_err(|err| Error::new(format!("couldnt read stdin: {}", err)))?;
+                    Ok(input)
+                })
+                .clone(),
+        }
+    }
+}
+
+/// run a problem solver on a watchdog thread and return its output, run time and heap usage
+fn solve_day(
+    input: &Arc<str>,
+    timeout: Option<Duration>,
+) -> impl Fn(Solver<String>) -> Outcome<String> + '_ {
+    move |solver| watchdog::run(solver, Arc::clone(input), timeout)
+}
+
+/// default untimed runs before benchmark sampling begins
+const BENCH_WARMUP: usize = 10;
+/// default timed runs per benchmark
+const BENCH_RUNS: usize = 100;
+
+/// look up a day's solution and read its input
+fn load_day(
+    year: usize,
+    day: usize,
+    source: &InputSource,
+) -> Result<(&'static dyn Day, Arc<str>), String> {
+    let solution = registry::get(year, day)
+        .ok_or_else(|| format!("{} day {:02} solution not found.", year, day))?;
+    let input = source
+        .load(year, solution.input())
+        .map_err(|err| format!("Day {:02}: {}", day, err))?;
+
+    Ok((solution, input.into()))
+}
+
+/// run the selected parts of a single day, returning a record of each part
 // i disagree about this readability concern.
 #[allow(clippy::option_map_unit_fn)]
-fn run_day(day: usize) -> Result<(), String> {
-    let idx = day.checked_sub(1).ok_or(format!("Invalid day {}", day))?;
-    let entry = SOLUTIONS
-        .get(idx)
-        .ok_or_else(|| format!("Day {:02} solution not found.", day))?;
-
-    entry
-        .as_ref()
-        .map(|solution| {
-            println!("Day {:02}:", day);
-            let run = solve_day(solution.input);
-            solution.one.map(|(text, solver)| {
-                let (result, dur) = run(solver);
-                println!("\tPart 1 - {}: {} ({:?})", text, result, dur);
-            });
-            solution.two.map(|(text, solver)| {
-                let (result, dur) = run(solver);
-                println!("\tPart 2 - {}: {} ({:?})", text, result, dur);
+fn run_day(
+    year: usize,
+    (day, parts): (usize, Parts),
+    source: &InputSource,
+    timeout: Option<Duration>,
+) -> Result<Vec<Record>, String> {
+    let (solution, input) = load_day(year, day, source)?;
+
+    let origin = source.name(year, solution.input());
+    let run = solve_day(&input, timeout);
+    let mut records = Vec::new();
+    let mut run_part = |part: usize, (label, solver)| {
+        records.push(to_record(year, day, part, label, &origin, run(solver)));
+    };
+    solution
+        .one()
+        .filter(|_| parts.one)
+        .map(|entry| run_part(1, entry));
+    solution
+        .two()
+        .filter(|_| parts.two)
+        .map(|entry| run_part(2, entry));
+
+    Ok(records)
+}
+
+/// record the output, run time and heap usage of one part of a day's solution
+fn to_record(
+    year: usize,
+    day: usize,
+    part: usize,
+    label: &'static str,
+    origin: &str,
+    outcome: Outcome<String>,
+) -> Record {
+    Record {
+        year,
+        day,
+        part,
+        label,
+        result: outcome
+            .result
+            .map_err(|err| err.with_day(day).with_part(part).with_file(origin)),
+        duration: outcome.duration,
+        memory: outcome.memory,
+    }
+}
+
+/// print the offending input of a record's parsing failure, if any
+fn print_excerpt(record: &Record) {
+    if let Err(Error {
+        parse: Some(parse), ..
+    }) = &record.result
+    {
+        for line in parse.excerpt().lines() {
+            println!("\t\t{}", line);
+        }
+    }
+}
+
+/// a part of a day's solution queued for a worker thread
+struct Task<'a> {
+    /// position of the day in the selection
+    idx: usize,
+    /// puzzle day
+    day: usize,
+    /// puzzle part
+    part: usize,
+    /// solution output label
+    label: &'static str,
+    /// part solver
+    solver: Solver<String>,
+    /// day puzzle input
+    input: &'a Arc<str>,
+    /// name of the day puzzle input
+    origin: String,
+}
+
+/// run the selected parts of every selected day of an event year on `jobs` worker threads,
+/// returning each day's records in day order
+#[allow(clippy::option_map_unit_fn)]
+fn run_days_parallel(
+    year: usize,
+    days: &[(usize, Parts)],
+    jobs: usize,
+    source: &InputSource,
+    timeout: Option<Duration>,
+) -> Vec<Result<Vec<Record>, String>> {
+    let loaded: Vec<_> = days
+        .iter()
+        .map(|&(day, _)| load_day(year, day, source))
+        .collect();
+
+    let mut tasks = Vec::new();
+    for (idx, (&(day, parts), entry)) in days.iter().zip(&loaded).enumerate() {
+        let Ok((solution, input)) = entry else {
+            continue;
+        };
+        let origin = source.name(year, solution.input());
+        let mut queue_part = |part: usize, (label, solver)| {
+            tasks.push(Task {
+                idx,
+                day,
+                part,
+                label,
+                solver,
+                input,
+                origin: origin.clone(),
             });
-        })
-        .ok_or_else(|| format!("Day {:02} solution not found.", day))
+        };
+        solution
+            .one()
+            .filter(|_| parts.one)
+            .map(|entry| queue_part(1, entry));
+        solution
+            .two()
+            .filter(|_| parts.two)
+            .map(|entry| queue_part(2, entry));
+    }
+
+    let records = pool::map_ordered(jobs, tasks, |task| {
+        let output = solve_day(task.input, timeout)(task.solver);
+        let record = to_record(year, task.day, task.part, task.label, &task.origin, output);
+        (task.idx, record)
+    });
+
+    let mut results: Vec<_> = loaded
+        .into_iter()
+        .map(|entry| entry.map(|_| Vec::new()))
+        .collect();
+    for (idx, record) in records {
+        if let Ok(day_records) = &mut results[idx] {
+            day_records.push(record);
+        }
+    }
+
+    results
 }
 
-/// run every day in range
-fn run_range(range: RangeInclusive<usize>) {
+/// the records of each selected day of an event year, in day order
+///
+/// With a single job each day runs when its records are requested, so output can be printed as
+/// the days finish. Otherwise every day runs up front on `jobs` worker threads.
+fn run_days<'a>(
+    year: usize,
+    days: &'a [(usize, Parts)],
+    options: &'a Options,
+) -> Box<dyn Iterator<Item = Result<Vec<Record>, String>> + 'a> {
+    if options.jobs > 1 {
+        Box::new(
+            run_days_parallel(year, days, options.jobs, &options.input, options.timeout)
+                .into_iter(),
+        )
+    } else {
+        Box::new(
+            days.iter()
+                .map(move |&day| run_day(year, day, &options.input, options.timeout)),
+        )
+    }
+}
+
+/// benchmark the selected parts of a single day, timing parsing and solving separately when the
+/// day exposes its parser
+///
+/// Each run goes through the watchdog, so a panic or a run over the time limit fails its part.
+#[allow(clippy::option_map_unit_fn)]
+fn bench_day(year: usize, (day, parts): (usize, Parts), options: &Options) -> Result<(), String> {
+    let (solution, input) = load_day(year, day, &options.input)?;
+    let timeout = options.timeout;
+    let run = solve_day(&input, timeout);
+
+    println!("Day {:02}:", day);
+    let mut failures = 0;
+    let mut bench_part = |part: usize,
+                          (text, solver): (&str, Solver<String>),
+                          phased: Option<PhasedSolver<String>>| {
+        let stats = match phased {
+            Some(phased) => bench::repeat(options.warmup, options.runs, || {
+                watchdog::run(phased, Arc::clone(&input), timeout)
+                    .result
+                    .map(|(_, times)| times)
+            })
+            .map(|times| {
+                let parse: Vec<_> = times.iter().map(|time| time.parse).collect();
+                let solve: Vec<_> = times.iter().map(|time| time.solve).collect();
+                vec![
+                    ("parse", bench::Stats::from_samples(&parse)),
+                    ("solve", bench::Stats::from_samples(&solve)),
+                ]
+            }),
+            None => bench::repeat(options.warmup, options.runs, || {
+                let outcome = run(solver);
+                outcome.result.map(|_| outcome.duration)
+            })
+            .map(|samples| vec![("total", bench::Stats::from_samples(&samples))]),
+        };
+
+        match stats {
+            Ok(stats) => {
+                println!("\tPart {} - {}:", part, text);
+                for (phase, stats) in stats {
+                    stats.map(|stats| println!("\t\t{}: {}", phase, stats));
+                }
+            }
+            Err(err) => {
+                let err = err.with_day(day).with_part(part);
+                println!("\tPart {} - {}: {}", part, text, err);
+                failures += 1;
+            }
+        }
+    };
+    solution
+        .one()
+        .filter(|_| parts.one)
+        .map(|entry| bench_part(1, entry, solution.phased_one()));
+    solution
+        .two()
+        .filter(|_| parts.two)
+        .map(|entry| bench_part(2, entry, solution.phased_two()));
+
+    if failures == 0 {
+        Ok(())
+    } else {
+        Err(format!("Day {:02}: {} part(s) failed.", day, failures))
+    }
+}
+
+/// run every day in range, returning `false` if any day failed
+fn run_range<T>(
+    range: impl IntoIterator<Item = T>,
+    format: Format,
+    mut run: impl FnMut(T) -> Result<(), String>,
+) -> bool {
+    let mut success = true;
     for day in range {
         // run single day
-        if let Err(msg) = run_day(day) {
-            println!("{}", msg);
+        if let Err(msg) = run(day) {
+            match format {
+                Format::Text => println!("{}", msg),
+                _ => eprintln!("{}", msg),
+            }
+            success = false;
+        }
+    }
+    if format == Format::Text {
+        println!();
+    }
+
+    success
+}
+
+/// run every day of an event year in range and report the results in the requested format
+fn report_range(
+    year: usize,
+    range: impl IntoIterator<Item = (usize, Parts)>,
+    options: &Options,
+) -> bool {
+    let format = options.format;
+    let days: Vec<_> = range.into_iter().collect();
+    let start = Instant::now();
+    let mut results = run_days(year, &days, options);
+
+    let mut records = Vec::new();
+    let success = run_range(days.iter().copied(), format, |(day, _)| {
+        let day_records = results
+            .next()
+            .unwrap_or_else(|| Err(format!("Day {:02}: no result.", day)))?;
+        if format == Format::Text {
+            println!("Day {:02}:", day);
+            for record in &day_records {
+                println!("\t{}", record);
+                print_excerpt(record);
+            }
+        }
+
+        let failures = day_records
+            .iter()
+            .filter(|record| record.result.is_err())
+            .count();
+        records.extend(day_records);
+
+        if failures == 0 {
+            Ok(())
+        } else {
+            Err(format!("Day {:02}: {} part(s) failed.", day, failures))
+        }
+    });
+
+    let wall = start.elapsed();
+
+    match format {
+        Format::Text if options.jobs > 1 => {
+            let solver: Duration = records.iter().map(|record| record.duration).sum();
+            println!(
+                "Wall time {:?}, summed solver time {:?} ({} jobs)",
+                wall, solver, options.jobs
+            );
+            println!();
+        }
+        Format::Text => (),
+        Format::Json => println!("{}", report::to_json(&records)),
+        Format::Csv => println!("{}", report::to_csv(&records)),
+    }
+
+    success
+}
+
+/// run every day of an event year in range and check the results against the expected answers
+fn verify_range(
+    year: usize,
+    range: impl IntoIterator<Item = (usize, Parts)>,
+    answers: &Answers,
+    options: &Options,
+) -> bool {
+    let days: Vec<_> = range.into_iter().collect();
+    let mut results = run_days(year, &days, options);
+
+    run_range(days.iter().copied(), Format::Text, |(day, _)| {
+        let records = results
+            .next()
+            .unwrap_or_else(|| Err(format!("Day {:02}: no result.", day)))?;
+
+        println!("Day {:02}:", day);
+        let mut failures = 0;
+        for record in &records {
+            let verdict = answers.verify(record);
+            println!("\tPart {} - {}: {}", record.part, record.label, verdict);
+            print_excerpt(record);
+            if verdict.is_failure() {
+                failures += 1;
+            }
+        }
+
+        if failures == 0 {
+            Ok(())
+        } else {
+            Err(format!("Day {:02}: {} part(s) failed.", day, failures))
+        }
+    })
+}
+
+/// Event year, selected days and parts, and a description of a day selection
+type Selection = (usize, Vec<(usize, Parts)>, String);
+
+/// the event year and days selected by a day selection command, and a description of the
+/// selection
+///
+/// Selections without an explicit year are for `year`
+fn day_range(year: usize, days: &Command) -> Option<Selection> {
+    if let Command::Year(year, days) = days {
+        return day_range(*year, days);
+    }
+
+    let selected = days.day_parts(&all_days(year))?;
+    let selection = match days {
+        Command::All => "all days".to_string(),
+        Command::Range(range) => format!("days {:?}", range),
+        Command::Day(day) => format!("day {}", day),
+        Command::Part(day, part) => format!("day {} part {}", day, part),
+        _ => format!("days {}", selection_text(days)),
+    };
+
+    Some((year, selected, format!("{} {}", year, selection)))
+}
+
+/// a day selection in command syntax, eg `1,3.2,4.1-6.2`
+fn selection_text(days: &Command) -> String {
+    match days {
+        Command::Day(day) => day.to_string(),
+        Command::Range(range) => format!("{}-{}", range.start(), range.end()),
+        Command::Part(day, part) => format!("{}.{}", day, part),
+        Command::PartRange((start, start_part), (end, end_part)) => {
+            format!("{}.{}-{}.{}", start, start_part, end, end_part)
+        }
+        Command::List(list) => list
+            .iter()
+            .map(selection_text)
+            .collect::<Vec<_>>()
+            .join(","),
+        _ => String::new(),
+    }
+}
+
+/// the event year and days selected by a day selection command, and a description of the
+/// selection, if the runner options can be applied to it
+///
+/// Selections without an explicit year are for the latest registered year
+fn select_days(days: &Command, options: &Options) -> Option<Selection> {
+    let (year, range, selection) = day_range(registry::latest_year(), days)?;
+    if options.input != InputSource::Directory && range.len() != 1 {
+        println!(
+            "An explicit input can only be used with a single day, not {}",
+            selection
+        );
+        return None;
+    }
+
+    Some((year, range, selection))
+}
+
+/// every registered day number of an event year
+fn all_days(year: usize) -> Vec<usize> {
+    registry::days_in(year).map(|day| day.number()).collect()
+}
+
+/// the sample input file names of a registered day, in name order
+fn sample_inputs(year: usize, day: usize) -> Vec<String> {
+    let prefix = format!("{:02}-t", day);
+    let mut samples: Vec<_> = fs::read_dir(input_dir(year))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+        .collect();
+    samples.sort();
+
+    samples
+}
+
+/// print every registered day with its title and available inputs
+fn list_days() {
+    for year in registry::years() {
+        println!("{}:", year);
+        for day in registry::days_in(year) {
+            let file = format!("{}.txt", day.input());
+            let input = if input_dir(year).join(&file).is_file() {
+                file
+            } else {
+                format!("{} missing", file)
+            };
+            let samples = sample_inputs(year, day.number());
+            let samples = if samples.is_empty() {
+                "no samples".to_string()
+            } else {
+                format!("samples {}", samples.join(", "))
+            };
+
+            println!(
+                "  Day {:02}: {:<30} input {}; {}",
+                day.number(),
+                day.title(),
+                input,
+                samples
+            );
         }
     }
     println!();
 }
 
+/// execute a command, returning `false` if any day failed
+fn run_command(command: Command, options: &Options) -> bool {
+    let format = options.format;
+    let announce = |msg: String| {
+        if format == Format::Text {
+            println!("{}", msg);
+        }
+    };
+
+    match command {
+        Command::Quit => true,
+        Command::Help => {
+            print_help();
+            true
+        }
+        Command::ListDays => {
+            list_days();
+            true
+        }
+        Command::Repeat => {
+            println!("No previous command to repeat");
+            false
+        }
+        Command::All
+        | Command::Range(_)
+        | Command::Day(_)
+        | Command::Part(..)
+        | Command::PartRange(..)
+        | Command::List(_)
+        | Command::Year(..) => {
+            let Some((year, range, selection)) = select_days(&command, options) else {
+                return false;
+            };
+            announce(format!("Running {}", selection));
+            report_range(year, range, options)
+        }
+        Command::Bench(days) => {
+            let Some((year, range, selection)) = select_days(&days, options) else {
+                return false;
+            };
+            println!(
+                "Benchmarking {} ({} runs after {} warmup runs)",
+                selection, options.runs, options.warmup
+            );
+            run_range(range, Format::Text, |day| bench_day(year, day, options))
+        }
+        Command::Verify(days) => {
+            let Some((year, range, selection)) = select_days(&days, options) else {
+                return false;
+            };
+            let answers_path = input_dir(year).join("answers.toml").display().to_string();
+            let answers = match Answers::load(&answers_path) {
+                Ok(answers) => answers,
+                Err(err) => {
+                    println!("Failed to load expected answers: {}", err);
+                    return false;
+                }
+            };
+            println!("Verifying {}", selection);
+            verify_range(year, range, &answers, options)
+        }
+    }
+}
+
+/// runner settings supplied as command line flags
+#[derive(Debug)]
+struct Options {
+    /// run report format
+    format: Format,
+    /// worker threads used to run a day selection, one runs the days in sequence
+    jobs: usize,
+    /// puzzle input source
+    input: InputSource,
+    /// file of interactive commands to run instead of an interactive session
+    script: Option<String>,
+    /// time limit for each solver run, if any
+    timeout: Option<Duration>,
+    /// timed runs per benchmark
+    runs: usize,
+    /// untimed runs before benchmark sampling begins
+    warmup: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            format: Format::default(),
+            jobs: 1,
+            input: InputSource::default(),
+            script: None,
+            timeout: Some(watchdog::DEFAULT_TIMEOUT),
+            runs: BENCH_RUNS,
+            warmup: BENCH_WARMUP,
+        }
+    }
+}
+
+impl Options {
+    /// separate option flags from the remaining command arguments
+    fn from_args(args: Vec<String>) -> Result<(Self, Vec<String>), String> {
+        let mut options = Self::default();
+        let mut rest = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if let Some(format) = arg.strip_prefix("--format=") {
+                options.format = format.parse()?;
+            } else if arg == "--format" || arg == "-f" {
+                let format = args.next().ok_or("Missing value for --format")?;
+                options.format = format.parse()?;
+            } else if let Some(input) = arg.strip_prefix("--input=") {
+                options.input = Self::parse_input(input);
+            } else if arg == "--input" || arg == "-i" {
+                let input = args.next().ok_or("Missing value for --input")?;
+                options.input = Self::parse_input(&input);
+            } else if let Some(script) = arg.strip_prefix("--script=") {
+                options.script = Some(script.to_string());
+            } else if arg == "--script" || arg == "-s" {
+                let script = args.next().ok_or("Missing value for --script")?;
+                options.script = Some(script);
+            } else if let Some(timeout) = arg.strip_prefix("--timeout=") {
+                options.timeout = Self::parse_timeout(timeout)?;
+            } else if arg == "--timeout" || arg == "-t" {
+                let timeout = args.next().ok_or("Missing value for --timeout")?;
+                options.timeout = Self::parse_timeout(&timeout)?;
+            } else if arg == "--parallel" || arg == "-p" {
+                options.jobs = pool::default_jobs();
+            } else if let Some(jobs) = arg.strip_prefix("--jobs=") {
+                options.jobs = Self::parse_jobs(jobs)?;
+            } else if arg == "--jobs" || arg == "-j" {
+                let jobs = args.next().ok_or("Missing value for --jobs")?;
+                options.jobs = Self::parse_jobs(&jobs)?;
+            } else if let Some(runs) = arg.strip_prefix("--runs=") {
+                options.runs = Self::parse_runs(runs)?;
+            } else if arg == "--runs" || arg == "-r" {
+                let runs = args.next().ok_or("Missing value for --runs")?;
+                options.runs = Self::parse_runs(&runs)?;
+            } else if let Some(warmup) = arg.strip_prefix("--warmup=") {
+                options.warmup = Self::parse_warmup(warmup)?;
+            } else if arg == "--warmup" || arg == "-w" {
+                let warmup = args.next().ok_or("Missing value for --warmup")?;
+                options.warmup = Self::parse_warmup(&warmup)?;
+            } else {
+                rest.push(arg);
+            }
+        }
+
+        if options.script.is_some() && !rest.is_empty() {
+            return Err("A script can't be combined with a command".to_string());
+        }
+
+        Ok((options, rest))
+    }
+
+    /// parse an input path, where `-` reads standard input
+    fn parse_input(input: &str) -> InputSource {
+        match input {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(path.to_string()),
+        }
+    }
+
+    /// parse a solver time limit in seconds, where 0 removes the limit
+    fn parse_timeout(timeout: &str) -> Result<Option<Duration>, String> {
+        match timeout.parse::<f64>() {
+            Ok(0.0) => Ok(None),
+            Ok(secs) if secs > 0.0 && secs.is_finite() => Ok(Some(Duration::from_secs_f64(secs))),
+            _ => Err(format!("Invalid timeout '{}'", timeout)),
+        }
+    }
+
+    /// parse a worker thread count
+    fn parse_jobs(jobs: &str) -> Result<usize, String> {
+        match jobs.parse() {
+            Ok(jobs) if jobs > 0 => Ok(jobs),
+            _ => Err(format!("Invalid job count '{}'", jobs)),
+        }
+    }
+
+    /// parse a benchmark run count
+    fn parse_runs(runs: &str) -> Result<usize, String> {
+        match runs.parse() {
+            Ok(runs) if runs > 0 => Ok(runs),
+            _ => Err(format!("Invalid run count '{}'", runs)),
+        }
+    }
+
+    /// parse a benchmark warmup run count, which may be 0
+    fn parse_warmup(warmup: &str) -> Result<usize, String> {
+        warmup
+            .parse()
+            .map_err(|_| format!("Invalid warmup run count '{}'", warmup))
+    }
+}
+
 /// clear the terminal
 fn clear_screen() {
-    const ESC: char = 0x1B as char;
     const SOFT_CLEAR: &str = "\x1B[2J\x1B[1;1H";
     print!("{}", SOFT_CLEAR);
 }
 
+/// print command line usage
+fn print_usage() {
+    eprintln!("Usage: advent_2023 [COMMAND]");
+    eprintln!("  --all  run all days");
+    eprintln!("  --list list the registered days and their available inputs");
+    eprintln!("  #      run a single day (eg 17)");
+    eprintln!("  #-#    run a range of days separated by a dash (eg 2-10)");
+    eprintln!("  #.#    run a single part of a day (eg 3.2), or a range of parts (eg 4.1-6.2)");
+    eprintln!("  #,#    run a comma separated list of the above (eg 1,3,5-7)");
+    eprintln!("  <YEAR> <DAYS>    run days of another event year (eg 2022 1-25, 2022 --all)");
+    eprintln!("  --bench <DAYS>   benchmark the selected days (eg --bench 3, --bench --all)");
+    eprintln!("  --verify <DAYS>  check the selected days against input/<YEAR>/answers.toml");
+    eprintln!();
+    eprintln!("Days are for the latest registered event year unless a year is given.");
+    eprintln!();
+    eprintln!("Options:");
+    eprintln!("  --format <text|json|csv>  run report format (default text)");
+    eprintln!("  --jobs <N>                run the selected days on N worker threads (default 1)");
+    eprintln!("  --parallel                run the selected days on one worker thread per core");
+    eprintln!("  --input <path>            read a single day's input from a file, or stdin for -");
+    eprintln!("  --script <path>           run a file of interactive commands, one per line");
+    eprintln!(
+        "  --timeout <SECS>          fail solvers running longer than SECS, 0 for no limit (default {})",
+        watchdog::DEFAULT_TIMEOUT.as_secs()
+    );
+    eprintln!(
+        "  --runs <N>                time N runs of each benchmarked part (default {})",
+        BENCH_RUNS
+    );
+    eprintln!(
+        "  --warmup <N>              run each benchmarked part N times untimed first (default {})",
+        BENCH_WARMUP
+    );
+    eprintln!();
+    eprintln!("Inputs are read from input/<YEAR>/ next to the binary, or under $AOC_INPUT_DIR.");
+    eprintln!();
+    eprintln!("Starts an interactive session when no command or script is supplied.");
+}
+
+/// execute a line of interactive input, tracking the last command for repeats
+///
+/// Breaks on quit, otherwise continues with `false` if the command was unrecognized or any day
+/// failed
+fn dispatch(
+    input: &str,
+    last_command: &mut Option<Command>,
+    options: &Options,
+) -> ControlFlow<(), bool> {
+    // an empty line repeats the previous command, like `!!`
+    let command = match input.trim() {
+        "" => Ok(Command::Repeat),
+        input => input.parse(),
+    };
+
+    let success = match command {
+        Ok(Command::Quit) => return ControlFlow::Break(()),
+        Ok(Command::Repeat) => match last_command {
+            Some(command) => run_command(command.clone(), options),
+            None => {
+                println!("No previous command to repeat");
+                false
+            }
+        },
+        Ok(command) => {
+            *last_command = Some(command.clone());
+            run_command(command, options)
+        }
+        Err(err) => {
+            println!("Unrecognized command '{}': {}", input.trim(), err);
+            false
+        }
+    };
+
+    ControlFlow::Continue(success)
+}
+
+/// execute each line of a script file of interactive commands, echoing each command, returning
+/// `false` if any command failed
+///
+/// Blank lines and lines starting with `#` are skipped.
+fn run_script(path: &str, options: &Options) -> bool {
+    let script = match read_file(path) {
+        Ok(script) => script,
+        Err(err) => {
+            println!("Failed to read script: {}", err);
+            return false;
+        }
+    };
+
+    let mut last_command = None;
+    let mut success = true;
+    for line in script.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        println!("-> {}", line);
+        match dispatch(line, &mut last_command, options) {
+            ControlFlow::Break(()) => break,
+            ControlFlow::Continue(command_success) => success &= command_success,
+        }
+    }
+
+    success
+}
+
+/// print the interactive command help
+fn print_help() {
+    println!("Commands, which may be abbreviated (eg b, ben, bench) and are case insensitive:");
+    println!("  a[ll]            run all days");
+    println!("  #                run a single day (eg 17)");
+    println!("  # - #            run a range of days separated by a dash (eg 2-10)");
+    println!("  #.#              run a single part of a day or a range of parts (eg 3.2, 4.1-6.2)");
+    println!("  #,#              run a comma separated list of the above (eg 1,3,5-7)");
+    println!("  yyyy ...         prefix any of the above with an event year (eg 2022 5)");
+    println!("  b[ench] ...      benchmark the selected days (eg b 4, bench 1-3)");
+    println!("  v[erify] ...     verify the selected days against known answers (eg v a)");
+    println!("  l[ist]           list the registered days and their available inputs");
+    println!("  !! or empty line repeat the previous command");
+    println!("  h[elp] or ?      show this help");
+    println!("  q[uit]           quit");
+    println!();
+}
+
 fn main() {
+    let (options, args) = match Options::from_args(env::args().skip(1).collect()) {
+        Ok(parsed) => parsed,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            print_usage();
+            process::exit(2);
+        }
+    };
+    if args.is_empty() && options.script.is_none() && options.input == InputSource::Stdin {
+        eprintln!("Reading input from stdin requires a command");
+        print_usage();
+        process::exit(2);
+    }
+    if !args.is_empty() {
+        // non-interactive mode
+        match Command::from_args(&args) {
+            Ok(command) => {
+                if !run_command(command, &options) {
+                    process::exit(1);
+                }
+            }
+            Err(err) => {
+                eprintln!("Unrecognized command '{}': {}", args.join(" "), err);
+                print_usage();
+                process::exit(2);
+            }
+        }
+        return;
+    }
+
+    if let Some(script) = &options.script {
+        if !run_script(script, &options) {
+            process::exit(1);
+        }
+        return;
+    }
+
     let mut input = String::new();
+    let mut last_command = None;
 
     clear_screen();
+    print_help();
     loop {
-        println!("Which day would you like to run?");
-        println!("  a      for all days");
-        println!("  #      or enter a day number (eg 17)");
-        println!("  # - #  or enter a day range separated by a dash (eg 2-10)");
-        println!("  q      to quit");
+        println!("Which day would you like to run? (h for help)");
         print!("-> ");
         io::stdout().flush().expect("Failed to flush stdout");
 
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read line");
-        clear_screen();
-
-        match input.parse() {
-            Ok(Command::Quit) => {
+        // end of input, eg ctrl-d or the end of piped commands, quits like `q`
+        match io::stdin().read_line(&mut input) {
+            Ok(0) => {
+                println!();
                 break;
             }
-            Ok(Command::All) => {
-                println!("Running all");
-                run_range(1..=25);
-            }
-            Ok(Command::Range(range)) => {
-                // run every day in range
-                println!("Running days {:?}", range);
-                run_range(range);
-            }
-            Ok(Command::Day(day)) => {
-                // run single day
-                println!("Running day {}", day);
-                run_range(day..=day);
-            }
-            _ => {
-                println!("Unrecognized command: '{}'", input.trim());
-                println!("  len {}", input.len());
-                println!("  buffer {}", input);
+            Ok(_) => (),
+            Err(err) => {
+                println!("Failed to read command: {}", err);
+                break;
             }
         }
+        clear_screen();
+
+        if dispatch(&input, &mut last_command, &options).is_break() {
+            break;
+        }
         input.clear();
     }
 }
@@ -0,0 +1,193 @@
+//! Heap usage measurement with a counting global allocator, enabled by the `alloc-stats` feature
+//!
+//! Allocations are counted per thread, so a measurement only covers the calling thread and not
+//! any threads it spawns.
+use std::fmt;
+
+/// Whether allocations are being counted
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+/// Heap usage of a measured closure
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AllocStats {
+    /// Largest number of bytes allocated at once
+    pub peak_bytes: usize,
+    /// Total bytes allocated
+    pub total_bytes: usize,
+    /// Number of allocations
+    pub allocations: usize,
+}
+
+/// run `f`, returning its output and heap usage, or `None` for the heap usage when allocations
+/// aren't counted
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    #[cfg(feature = "alloc-stats")]
+    {
+        counting::reset();
+        let output = f();
+        (output, Some(counting::stats()))
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        (f(), None)
+    }
+}
+
+/// format a byte count with a binary unit, eg `1.5 KiB`
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "peak {}, {} in {} allocs",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes),
+            self.allocations
+        )
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    use super::AllocStats;
+
+    /// System allocator which counts the allocations of each thread
+    struct CountingAlloc;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAlloc = CountingAlloc;
+
+    thread_local! {
+        /// Bytes currently allocated since the last reset, which goes negative when memory
+        /// allocated before the reset is freed
+        static CURRENT: Cell<isize> = const { Cell::new(0) };
+        static PEAK: Cell<usize> = const { Cell::new(0) };
+        static TOTAL: Cell<usize> = const { Cell::new(0) };
+        static COUNT: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// record an allocation of `size` bytes, and the release of `freed` bytes
+    fn record(size: usize, freed: usize) {
+        // thread locals may already be gone while a thread shuts down
+        let _ = CURRENT.try_with(|current| {
+            let now = current.get() + size as isize - freed as isize;
+            current.set(now);
+            PEAK.with(|peak| peak.set(peak.get().max(now.max(0) as usize)));
+            TOTAL.with(|total| total.set(total.get() + size));
+            COUNT.with(|count| count.set(count.get() + 1));
+        });
+    }
+
+    /// record the release of `size` bytes
+    fn record_free(size: usize) {
+        let _ = CURRENT.try_with(|current| current.set(current.get() - size as isize));
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record(layout.size(), 0);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record(layout.size(), 0);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record_free(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record(new_size, layout.size());
+            }
+            new_ptr
+        }
+    }
+
+    /// start counting the current thread's allocations from zero
+    pub fn reset() {
+        CURRENT.with(|current| current.set(0));
+        PEAK.with(|peak| peak.set(0));
+        TOTAL.with(|total| total.set(0));
+        COUNT.with(|count| count.set(0));
+    }
+
+    /// the current thread's heap usage since the last reset
+    pub fn stats() -> AllocStats {
+        AllocStats {
+            peak_bytes: PEAK.with(Cell::get),
+            total_bytes: TOTAL.with(Cell::get),
+            allocations: COUNT.with(Cell::get),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let msg = "should format byte counts with binary units";
+        let stats = AllocStats {
+            peak_bytes: 1536,
+            total_bytes: 3 * 1024 * 1024,
+            allocations: 12,
+        };
+        let expected = "peak 1.5 KiB, 3.0 MiB in 12 allocs";
+        assert_eq!(stats.to_string(), expected, "{}", msg);
+        assert_eq!(format_bytes(512), "512 B", "{}", msg);
+    }
+
+    #[test]
+    fn measure_allocations() {
+        let (len, stats) = measure(|| {
+            let mut list: Vec<u64> = Vec::with_capacity(1000);
+            list.extend(0..1000);
+            drop(list);
+            vec![0_u8; 100].len()
+        });
+        assert_eq!(len, 100);
+
+        let msg = "should only measure with the alloc-stats feature";
+        assert_eq!(stats.is_some(), ENABLED, "{}", msg);
+
+        if let Some(stats) = stats {
+            let msg = "should count the peak, total and number of allocations";
+            assert!(stats.peak_bytes >= 8000, "{} {:?}", msg, stats);
+            assert!(stats.peak_bytes < 8100, "{} {:?}", msg, stats);
+            assert!(stats.total_bytes >= 8100, "{} {:?}", msg, stats);
+            assert!(stats.allocations >= 2, "{} {:?}", msg, stats);
+        }
+    }
+}
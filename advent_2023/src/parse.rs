@@ -0,0 +1,185 @@
+//! Reusable puzzle input parsers built on the `parser` combinator library
+use parser::three::lib::{
+    any_of, keep_first, keep_second, p_char, p_string, sep_by, sep_by1, spaces, spaces1, Parser,
+};
+
+use crate::ParseError;
+
+/// Decimal digits
+const DIGITS: [char; 10] = ['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+
+/// parse an unsigned decimal integer, failing on a sign rather than wrapping a negative
+pub fn p_usize() -> Parser<usize> {
+    // digits with nothing between them
+    sep_by1(any_of(DIGITS), p_string("")).map(|digits| {
+        digits
+            .into_iter()
+            .filter_map(|digit| digit.to_digit(10))
+            .fold(0, |n, digit| n * 10 + digit as usize)
+    })
+}
+
+/// parse one or more `item`s separated by commas and optional whitespace
+pub fn comma_list<T: 'static>(item: Parser<T>) -> Parser<Vec<T>> {
+    delimited_list(item, ',')
+}
+
+/// parse one or more `item`s separated by `delimiter` and optional whitespace
+pub fn delimited_list<T: 'static>(item: Parser<T>, delimiter: char) -> Parser<Vec<T>> {
+    sep_by1(item, keep_first(p_char(delimiter), spaces()))
+}
+
+/// parse whitespace separated unsigned integers, skipping leading whitespace
+pub fn ints() -> Parser<Vec<usize>> {
+    keep_second(spaces(), sep_by(p_usize(), spaces1()))
+}
+
+/// parse a `label: body` line, returning the label and body
+pub fn labeled<L: 'static, T: 'static>(label: Parser<L>, body: Parser<T>) -> Parser<(L, T)> {
+    keep_first(label, keep_first(p_char(':'), spaces())).and_then(body)
+}
+
+/// run `parser` on the whole of `input`, failing if any input is left over
+///
+/// Leftover input is reported at the first unparsed char. Failures inside the combinator
+/// library are reported at the start of the input, so line parsers which should locate a
+/// malformed token leave it unparsed, see [`parse_lines_expecting`].
+pub fn parse_all<T: 'static>(parser: &Parser<T>, input: &str) -> Result<T, ParseError> {
+    match parser.parse(input) {
+        Ok((rest, value)) if rest.trim().is_empty() => Ok(value),
+        Ok((rest, _)) => {
+            let rest = rest.trim_start();
+            let unexpected = rest.lines().next().unwrap_or_default();
+            Err(ParseError::at_offset(
+                input,
+                input.len() - rest.len(),
+                format!("Unexpected '{}'", unexpected),
+            ))
+        }
+        Err(err) => Err(ParseError::at_offset(input, 0, err.to_string())),
+    }
+}
+
+/// run `parser` on each line of `input`, tagging failures with their line number
+pub fn parse_lines_with<'a, T: 'static>(
+    input: &'a str,
+    parser: &'a Parser<T>,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a {
+    input.lines().enumerate().map(move |(idx, line)| {
+        parse_all(parser, line).map_err(|err| ParseError {
+            line: idx + 1,
+            ..err
+        })
+    })
+}
+
+/// run `parser` on each line of `input`, where a `None` result is a line which ended before
+/// its `expected` item
+///
+/// Wrapping a required item in `opt` leaves a malformed item as unparsed input, which is
+/// reported at its own column rather than at the start of the line.
+pub fn parse_lines_expecting<'a, T: 'static>(
+    input: &'a str,
+    parser: &'a Parser<Option<T>>,
+    expected: &'a str,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a {
+    input
+        .lines()
+        .zip(parse_lines_with(input, parser))
+        .enumerate()
+        .map(move |(idx, (line, result))| {
+            result?.ok_or_else(|| ParseError {
+                line: idx + 1,
+                ..ParseError::at_offset(
+                    line,
+                    line.trim_end().len(),
+                    format!("Expected {}", expected),
+                )
+            })
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use parser::three::lib::opt;
+
+    #[test]
+    fn unsigned() {
+        let msg = "should parse an unsigned integer";
+        let actual = parse_all(&p_usize(), "0042").unwrap();
+        assert_eq!(actual, 42, "{}", msg);
+
+        let msg = "should reject a negative integer";
+        assert!(parse_all(&p_usize(), "-3").is_err(), "{}", msg);
+        let actual = parse_all(&ints(), "4 -3").unwrap_err();
+        assert_eq!(actual.column, 3, "{}", msg);
+    }
+
+    #[test]
+    fn lists() {
+        let msg = "should parse a comma separated list";
+        let expected = vec![1, 22, 3];
+        let actual = parse_all(&comma_list(p_usize()), "1, 22,3").unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should parse whitespace separated integers";
+        let expected = vec![41, 48, 6];
+        let actual = parse_all(&ints(), "  41 48  6 ").unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should parse a list with a custom delimiter";
+        let expected = vec![vec![1, 2], vec![3]];
+        let actual = parse_all(&delimited_list(comma_list(p_usize()), ';'), "1, 2; 3").unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn labeled_line() {
+        let msg = "should split a line into its label and body";
+        let label = keep_second(p_string("Card").and_then(spaces1()), p_usize());
+        let parser = labeled(label, ints());
+        let expected = (12, vec![5, 6]);
+        let actual = parse_all(&parser, "Card  12: 5 6").unwrap();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should report leftover input";
+        let actual = parse_all(&parser, "Card  12: 5 6 | 7").unwrap_err();
+        assert_eq!(actual.column, 15, "{}", msg);
+        assert_eq!(actual.reason, "Unexpected '| 7'", "{}", msg);
+
+        let msg = "should report the line of each failure";
+        let actual: Vec<_> = parse_lines_with("Card 1: 2\nCard 2 3", &parser)
+            .map(|card| card.map_err(|err| err.line))
+            .collect();
+        assert_eq!(actual, vec![Ok((1, vec![2])), Err(2)], "{}", msg);
+    }
+
+    #[test]
+    fn expected_item() {
+        let label = keep_second(p_string("Card").and_then(spaces1()), p_usize());
+        let parser = labeled(
+            label,
+            ints().and_then(opt(keep_second(p_char('|'), ints()))),
+        )
+        .map(|(_, (winners, have))| Some((winners, have?)));
+
+        let msg = "should report a malformed item at its own column";
+        let actual = parse_lines_expecting("Card 1: 2 x| 3", &parser, "'|'")
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(actual.column, 11, "{}", msg);
+        assert_eq!(actual.reason, "Unexpected 'x| 3'", "{}", msg);
+
+        let msg = "should report a missing item at the end of its line";
+        let actual: Vec<_> = parse_lines_expecting("Card 1: 2| 3\nCard 2: 4 ", &parser, "'|'")
+            .map(|card| card.map_err(|err| (err.line, err.column, err.reason)))
+            .collect();
+        let expected = vec![
+            Ok((vec![2], vec![3])),
+            Err((2, 10, "Expected '|'".to_string())),
+        ];
+        assert_eq!(actual, expected, "{}", msg);
+    }
+}
@@ -0,0 +1,90 @@
+//! Fixed size worker pool built on scoped std threads
+use std::{
+    num::NonZeroUsize,
+    panic,
+    sync::Mutex,
+    thread::{self, available_parallelism},
+};
+
+/// number of worker threads to use when none is requested, one per available core
+pub fn default_jobs() -> usize {
+    available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// apply `f` to every item on up to `jobs` worker threads, returning the outputs in item order
+///
+/// Workers take the next pending item as they become free, so long running items don't hold up
+/// the rest of the queue. A panic in `f` is resumed on the calling thread once every worker has
+/// stopped.
+pub fn map_ordered<T, R, F>(jobs: usize, items: Vec<T>, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let count = items.len();
+    let jobs = jobs.clamp(1, count.max(1));
+    if jobs == 1 {
+        return items.into_iter().map(f).collect();
+    }
+
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let next = || queue.lock().unwrap_or_else(|err| err.into_inner()).next();
+
+    let mut outputs: Vec<(usize, R)> = Vec::with_capacity(count);
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut outputs = Vec::new();
+                    while let Some((idx, item)) = next() {
+                        outputs.push((idx, f(item)));
+                    }
+                    outputs
+                })
+            })
+            .collect();
+
+        let mut panicked = None;
+        for worker in workers {
+            match worker.join() {
+                Ok(worker_outputs) => outputs.extend(worker_outputs),
+                Err(payload) => panicked = Some(payload),
+            }
+        }
+        if let Some(payload) = panicked {
+            panic::resume_unwind(payload);
+        }
+    });
+
+    outputs.sort_unstable_by_key(|(idx, _)| *idx);
+    outputs.into_iter().map(|(_, output)| output).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{thread::sleep, time::Duration};
+
+    use super::*;
+
+    #[test]
+    fn ordered_outputs() {
+        let msg = "should return the outputs in item order";
+        let expected: Vec<_> = (0..20).map(|n| n * n).collect();
+        let actual = map_ordered(4, (0..20).collect(), |n: u64| {
+            // finish later items first
+            sleep(Duration::from_millis(20 - n));
+            n * n
+        });
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should run on the calling thread with a single job";
+        let caller = thread::current().id();
+        let actual = map_ordered(1, vec![(); 3], |_| thread::current().id() == caller);
+        assert_eq!(actual, vec![true; 3], "{}", msg);
+
+        let msg = "should handle an empty queue";
+        let actual = map_ordered(8, Vec::<u8>::new(), |n| n);
+        assert!(actual.is_empty(), "{}", msg);
+    }
+}
@@ -0,0 +1,320 @@
+//! Sets of integers stored as sorted, disjoint ranges
+use std::{
+    fmt,
+    ops::{Range, RangeInclusive},
+};
+
+/// Set of integers stored as sorted, disjoint, non-adjacent half-open ranges
+#[derive(Clone, Default, Eq, Hash, PartialEq)]
+pub struct RangeSet {
+    ranges: Vec<Range<i64>>,
+}
+
+impl RangeSet {
+    /// create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the ranges of the set, in ascending order
+    pub fn ranges(&self) -> &[Range<i64>] {
+        &self.ranges
+    }
+
+    /// whether the set has no members
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// number of members of the set
+    pub fn count(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|range| range.start.abs_diff(range.end))
+            .sum()
+    }
+
+    /// whether `value` is a member of the set
+    pub fn contains(&self, value: i64) -> bool {
+        // first range ending after value
+        let idx = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges
+            .get(idx)
+            .is_some_and(|range| range.contains(&value))
+    }
+
+    /// add every member of `range` to the set
+    pub fn insert(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // ranges overlapping or adjacent to the new range are merged into it
+        let first = self.ranges.partition_point(|other| other.end < range.start);
+        let last = self
+            .ranges
+            .partition_point(|other| other.start <= range.end);
+        let merged = self.ranges[first..last]
+            .iter()
+            .fold(range, |merged, other| {
+                merged.start.min(other.start)..merged.end.max(other.end)
+            });
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// remove every member of `range` from the set
+    pub fn remove(&mut self, range: Range<i64>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self
+            .ranges
+            .partition_point(|other| other.end <= range.start);
+        let last = self.ranges.partition_point(|other| other.start < range.end);
+        let remaining: Vec<_> = self.ranges[first..last]
+            .iter()
+            .flat_map(|other| {
+                let (before, _, after) = split(other.clone(), range.clone());
+                [before, after]
+            })
+            .flatten()
+            .collect();
+        self.ranges.splice(first..last, remaining);
+    }
+
+    /// members of either set
+    pub fn union(&self, other: &RangeSet) -> RangeSet {
+        let mut result = self.clone();
+        other
+            .ranges
+            .iter()
+            .for_each(|range| result.insert(range.clone()));
+
+        result
+    }
+
+    /// members of both sets
+    pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+        let mut result = RangeSet::new();
+        let (mut left, mut right) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+        while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+            let overlap = a.start.max(b.start)..a.end.min(b.end);
+            if !overlap.is_empty() {
+                result.ranges.push(overlap);
+            }
+            // advance whichever range ends first
+            if a.end < b.end {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+
+        result
+    }
+
+    /// members of this set which are not in `other`
+    pub fn difference(&self, other: &RangeSet) -> RangeSet {
+        let mut result = self.clone();
+        other
+            .ranges
+            .iter()
+            .for_each(|range| result.remove(range.clone()));
+
+        result
+    }
+
+    /// map every member through an offset table
+    ///
+    /// Members within a table range are shifted by its offset, and all other members are
+    /// unchanged. Table ranges are expected not to overlap.
+    pub fn map_offsets(&self, table: &[(Range<i64>, i64)]) -> RangeSet {
+        let mut unmapped = self.clone();
+        let mut result = RangeSet::new();
+        for (source, offset) in table {
+            for range in self.intersection(&RangeSet::from(source.clone())).ranges {
+                unmapped.remove(range.clone());
+                result.insert(range.start + offset..range.end + offset);
+            }
+        }
+
+        result.union(&unmapped)
+    }
+}
+
+/// Parts of a range before, within and after another range, if not empty
+pub type Split = (Option<Range<i64>>, Option<Range<i64>>, Option<Range<i64>>);
+
+/// split `range` into the parts before, within and after `by`
+pub fn split(range: Range<i64>, by: Range<i64>) -> Split {
+    let non_empty = |range: Range<i64>| Some(range).filter(|range| !range.is_empty());
+
+    (
+        non_empty(range.start..range.end.min(by.start)),
+        non_empty(range.start.max(by.start)..range.end.min(by.end)),
+        non_empty(range.start.max(by.end)..range.end),
+    )
+}
+
+impl From<Range<i64>> for RangeSet {
+    fn from(range: Range<i64>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+
+        set
+    }
+}
+
+impl From<RangeInclusive<i64>> for RangeSet {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        Self::from(*range.start()..*range.end() + 1)
+    }
+}
+
+impl FromIterator<Range<i64>> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|range| set.insert(range));
+
+        set
+    }
+}
+
+impl fmt::Debug for RangeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.ranges).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    /// members in the domain used by the property tests
+    const DOMAIN: Range<i64> = -20..60;
+
+    /// minimal linear congruential generator, so property tests are repeatable
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn below(&mut self, bound: i64) -> i64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((self.0 >> 33) % bound as u64) as i64
+        }
+
+        /// a random set of up to 5 ranges within `0..40`
+        fn range_set(&mut self) -> RangeSet {
+            (0..self.below(6))
+                .map(|_| {
+                    let start = self.below(40);
+                    start..start + self.below(10)
+                })
+                .collect()
+        }
+    }
+
+    fn members(set: &RangeSet) -> BTreeSet<i64> {
+        DOMAIN.filter(|value| set.contains(*value)).collect()
+    }
+
+    fn is_normalized(set: &RangeSet) -> bool {
+        set.ranges().iter().all(|range| !range.is_empty())
+            && set
+                .ranges()
+                .windows(2)
+                .all(|pair| pair[0].end < pair[1].start)
+    }
+
+    #[test]
+    fn insert() {
+        let msg = "should merge overlapping and adjacent ranges";
+        let actual: RangeSet = [5..8, 1..3, 3..4, 10..12, 7..10].into_iter().collect();
+        assert_eq!(actual.ranges(), &[1..4, 5..12], "{}", msg);
+        assert_eq!(actual.count(), 10, "{}", msg);
+
+        let msg = "should test membership";
+        assert!(actual.contains(11), "{}", msg);
+        assert!(!actual.contains(4), "{}", msg);
+        assert!(!actual.contains(12), "{}", msg);
+    }
+
+    #[test]
+    fn split_range() {
+        let msg = "should split a range by another";
+        let expected = (Some(0..3), Some(3..5), Some(5..10));
+        assert_eq!(split(0..10, 3..5), expected, "{}", msg);
+        let expected = (None, Some(2..4), None);
+        assert_eq!(split(2..4, 0..10), expected, "{}", msg);
+        let expected = (Some(0..4), None, None);
+        assert_eq!(split(0..4, 6..10), expected, "{}", msg);
+    }
+
+    #[test]
+    fn map_offsets() {
+        let msg = "should shift members within each table range by its offset";
+        let seeds: RangeSet = [79..93, 55..68].into_iter().collect();
+        let table = [(98..100, -48), (50..98, 2)];
+        let expected: RangeSet = [81..95, 57..70].into_iter().collect();
+        let actual = seeds.map_offsets(&table);
+        assert_eq!(actual, expected, "{}", msg);
+
+        let msg = "should leave members outside the table unchanged";
+        let expected: RangeSet = [0..5, 10..15].into_iter().collect();
+        let actual = RangeSet::from(0..10).map_offsets(&[(5..10, 5)]);
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn set_properties() {
+        let mut rng = Lcg(2023);
+        for _ in 0..500 {
+            let (a, b) = (rng.range_set(), rng.range_set());
+            let (set_a, set_b) = (members(&a), members(&b));
+
+            let msg = "union should hold the members of either set";
+            let actual = a.union(&b);
+            assert!(is_normalized(&actual), "{} {:?} {:?}", msg, a, b);
+            let expected: BTreeSet<_> = set_a.union(&set_b).copied().collect();
+            assert_eq!(members(&actual), expected, "{} {:?} {:?}", msg, a, b);
+
+            let msg = "intersection should hold the members of both sets";
+            let actual = a.intersection(&b);
+            assert!(is_normalized(&actual), "{} {:?} {:?}", msg, a, b);
+            let expected: BTreeSet<_> = set_a.intersection(&set_b).copied().collect();
+            assert_eq!(members(&actual), expected, "{} {:?} {:?}", msg, a, b);
+
+            let msg = "difference should hold the members of only the first set";
+            let actual = a.difference(&b);
+            assert!(is_normalized(&actual), "{} {:?} {:?}", msg, a, b);
+            let expected: BTreeSet<_> = set_a.difference(&set_b).copied().collect();
+            assert_eq!(members(&actual), expected, "{} {:?} {:?}", msg, a, b);
+
+            let msg = "member counts should add up";
+            let actual = a.union(&b).count() + a.intersection(&b).count();
+            assert_eq!(actual, a.count() + b.count(), "{} {:?} {:?}", msg, a, b);
+
+            let msg = "mapping should shift each member by its table offset";
+            let offset = rng.below(20) - 10;
+            let table = [(10..20, offset), (25..30, -offset)];
+            let actual = a.map_offsets(&table);
+            let expected: BTreeSet<_> = set_a
+                .iter()
+                .map(|value| match value {
+                    10..=19 => value + offset,
+                    25..=29 => value - offset,
+                    _ => *value,
+                })
+                .collect();
+            assert_eq!(members(&actual), expected, "{} {:?}", msg, a);
+        }
+    }
+}
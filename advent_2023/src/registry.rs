@@ -0,0 +1,155 @@
+//! Registry of solved puzzle days
+use crate::{Day, YEAR};
+
+/// Every solved day, in year and day order
+static DAYS: &[&dyn Day] = &[
+    &crate::day_01::SOLUTION,
+    &crate::day_02::SOLUTION,
+    &crate::day_03::SOLUTION,
+    &crate::day_04::SOLUTION,
+];
+
+/// every registered day, in year and day order
+pub fn days() -> impl Iterator<Item = &'static dyn Day> {
+    DAYS.iter().copied()
+}
+
+/// every registered day of an event year, in day order
+pub fn days_in(year: usize) -> impl Iterator<Item = &'static dyn Day> {
+    days_of(DAYS, year)
+}
+
+/// look up a registered day by its event year and number
+pub fn get(year: usize, day: usize) -> Option<&'static dyn Day> {
+    find(DAYS, year, day)
+}
+
+/// every event year with a registered day, in ascending order
+pub fn years() -> Vec<usize> {
+    years_of(DAYS)
+}
+
+/// the most recent event year with a registered day, or the crate's [`YEAR`] if there are none
+pub fn latest_year() -> usize {
+    latest_of(DAYS)
+}
+
+/// every day of an event year in `list`
+fn days_of(
+    list: &'static [&'static dyn Day],
+    year: usize,
+) -> impl Iterator<Item = &'static dyn Day> {
+    list.iter()
+        .copied()
+        .filter(move |entry| entry.year() == year)
+}
+
+/// look up a day in `list` by its event year and number
+fn find(list: &'static [&'static dyn Day], year: usize, day: usize) -> Option<&'static dyn Day> {
+    days_of(list, year).find(|entry| entry.number() == day)
+}
+
+/// every event year with a day in `list`, in ascending order
+fn years_of(list: &[&dyn Day]) -> Vec<usize> {
+    let mut years: Vec<_> = list.iter().map(|entry| entry.year()).collect();
+    years.sort_unstable();
+    years.dedup();
+
+    years
+}
+
+/// the most recent event year with a day in `list`, or the crate's [`YEAR`] if it is empty
+fn latest_of(list: &[&dyn Day]) -> usize {
+    list.iter().map(|entry| entry.year()).max().unwrap_or(YEAR)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Solution, FIRST_YEAR};
+
+    /// an unsolved day of an event year
+    const fn fixture(year: usize, day: usize) -> Solution<String> {
+        Solution {
+            year,
+            day,
+            title: "",
+            input: "",
+            one: None,
+            two: None,
+            phased_one: None,
+            phased_two: None,
+        }
+    }
+
+    static FIXTURE: &[&dyn Day] = &[
+        &fixture(2015, 1),
+        &fixture(2015, 2),
+        &fixture(2021, 1),
+        &fixture(2021, 2),
+        &fixture(2021, 25),
+    ];
+
+    #[test]
+    fn registered_days() {
+        let msg = "should register each day once, in year and day order";
+        let numbers: Vec<_> = days().map(|day| (day.year(), day.number())).collect();
+        let mut expected = numbers.clone();
+        expected.sort_unstable();
+        expected.dedup();
+        assert_eq!(numbers, expected, "{}", msg);
+
+        let msg = "should look up a day by its year and number";
+        let actual = get(2023, 3).map(|day| day.title());
+        assert_eq!(actual, Some("Gear Ratios"), "{}", msg);
+        assert!(get(2023, 0).is_none(), "{}", msg);
+        assert!(get(FIRST_YEAR - 1, 3).is_none(), "{}", msg);
+
+        let msg = "should list the registered event years";
+        assert!(years().contains(&2023), "{}", msg);
+        assert!(latest_year() >= 2023, "{}", msg);
+    }
+
+    #[test]
+    fn every_day_module() {
+        let msg = "should register every day module declared in lib.rs";
+        let modules: Vec<_> = include_str!("lib.rs")
+            .lines()
+            .filter_map(|line| line.strip_prefix("pub mod ")?.strip_suffix(';'))
+            .filter_map(|module| match module.split_once("_day_") {
+                Some((year, day)) => Some((module, year.strip_prefix('y')?.parse().ok()?, day)),
+                None => Some((module, YEAR, module.strip_prefix("day_")?)),
+            })
+            .collect();
+        for (module, year, day) in &modules {
+            let day = day.parse().unwrap();
+            assert!(
+                get(*year, day).is_some(),
+                "{}: {} is missing from DAYS",
+                msg,
+                module
+            );
+        }
+        assert_eq!(days().count(), modules.len(), "{}", msg);
+    }
+
+    #[test]
+    fn multiple_years() {
+        let msg = "should list the days of one event year";
+        let actual: Vec<_> = days_of(FIXTURE, 2021).map(|day| day.number()).collect();
+        assert_eq!(actual, vec![1, 2, 25], "{}", msg);
+        assert_eq!(days_of(FIXTURE, 2016).count(), 0, "{}", msg);
+
+        let msg = "should look up a day in its own event year";
+        let actual = find(FIXTURE, 2015, 2).map(|day| (day.year(), day.number()));
+        assert_eq!(actual, Some((2015, 2)), "{}", msg);
+        assert!(find(FIXTURE, 2015, 25).is_none(), "{}", msg);
+
+        let msg = "should list each event year once, in ascending order";
+        assert_eq!(years_of(FIXTURE), vec![2015, 2021], "{}", msg);
+
+        let msg = "should find the latest event year, or the crate's year without any days";
+        assert_eq!(latest_of(FIXTURE), 2021, "{}", msg);
+        assert_eq!(latest_of(&[]), YEAR, "{}", msg);
+    }
+}
@@ -0,0 +1,278 @@
+//! Machine-readable run reports
+use std::{fmt, str::FromStr, time::Duration};
+
+use crate::{memory::AllocStats, Error, ErrorKind};
+
+/// Run report output format
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    /// Human readable text
+    #[default]
+    Text,
+    /// JSON array of records
+    Json,
+    /// CSV records with a header row
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown output format '{}'", s)),
+        }
+    }
+}
+
+/// Outcome of a single solver invocation
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    /// Event year
+    pub year: usize,
+    /// Puzzle day
+    pub day: usize,
+    /// Puzzle part
+    pub part: usize,
+    /// Solution output label
+    pub label: &'static str,
+    /// Solver answer or failure
+    pub result: Result<String, Error>,
+    /// Solver run time
+    pub duration: Duration,
+    /// Solver heap usage, when allocations are counted
+    pub memory: Option<AllocStats>,
+}
+
+impl Record {
+    /// short status of the solver invocation
+    pub fn status(&self) -> &'static str {
+        match &self.result {
+            Ok(_) => "ok",
+            Err(err) => match err.kind {
+                ErrorKind::Failed => "error",
+                ErrorKind::Panic => "panic",
+                ErrorKind::Timeout => "timeout",
+            },
+        }
+    }
+
+    /// peak bytes, allocated bytes and allocation count, when allocations are counted
+    fn memory_fields(&self) -> [Option<String>; 3] {
+        match self.memory {
+            Some(stats) => [stats.peak_bytes, stats.total_bytes, stats.allocations]
+                .map(|field| Some(field.to_string())),
+            None => [None, None, None],
+        }
+    }
+
+    /// format as a JSON object
+    pub fn to_json(&self) -> String {
+        let (answer, error) = match &self.result {
+            Ok(answer) => (json_string(answer), "null".to_string()),
+            Err(err) => ("null".to_string(), json_string(&err.to_string())),
+        };
+
+        let [peak_bytes, alloc_bytes, allocs] = self
+            .memory_fields()
+            .map(|field| field.unwrap_or_else(|| "null".to_string()));
+
+        format!(
+            r#"{{"year":{},"day":{},"part":{},"label":{},"answer":{},"duration_ns":{},"peak_bytes":{},"alloc_bytes":{},"allocs":{},"status":{},"error":{}}}"#,
+            self.year,
+            self.day,
+            self.part,
+            json_string(self.label),
+            answer,
+            self.duration.as_nanos(),
+            peak_bytes,
+            alloc_bytes,
+            allocs,
+            json_string(self.status()),
+            error,
+        )
+    }
+
+    /// format as a CSV row matching [`CSV_HEADER`]
+    pub fn to_csv(&self) -> String {
+        let (answer, error) = match &self.result {
+            Ok(answer) => (answer.clone(), String::new()),
+            Err(err) => (String::new(), err.to_string()),
+        };
+
+        let [peak_bytes, alloc_bytes, allocs] = self.memory_fields().map(Option::unwrap_or_default);
+
+        [
+            self.year.to_string(),
+            self.day.to_string(),
+            self.part.to_string(),
+            self.label.to_string(),
+            answer,
+            self.duration.as_nanos().to_string(),
+            peak_bytes,
+            alloc_bytes,
+            allocs,
+            self.status().to_string(),
+            error,
+        ]
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",")
+    }
+}
+
+impl fmt::Display for Record {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.result, self.memory) {
+            (Ok(answer), Some(memory)) => write!(
+                f,
+                "Part {} - {}: {} ({:?}, {})",
+                self.part, self.label, answer, self.duration, memory
+            ),
+            (Ok(answer), None) => write!(
+                f,
+                "Part {} - {}: {} ({:?})",
+                self.part, self.label, answer, self.duration
+            ),
+            (Err(err), _) => write!(f, "Part {} - {}: {}", self.part, self.label, err),
+        }
+    }
+}
+
+/// CSV column names
+pub const CSV_HEADER: &str =
+    "year,day,part,label,answer,duration_ns,peak_bytes,alloc_bytes,allocs,status,error";
+
+/// format records as a JSON array
+pub fn to_json(records: &[Record]) -> String {
+    let records = records
+        .iter()
+        .map(|record| format!("  {}", record.to_json()))
+        .collect::<Vec<_>>();
+
+    if records.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", records.join(",\n"))
+    }
+}
+
+/// format records as CSV with a header row
+pub fn to_csv(records: &[Record]) -> String {
+    std::iter::once(CSV_HEADER.to_string())
+        .chain(records.iter().map(Record::to_csv))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// quote and escape a JSON string
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            ch if ch.is_control() => result.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => result.push(ch),
+        }
+    }
+    result.push('"');
+
+    result
+}
+
+/// quote a CSV field if it contains a delimiter, quote or line break
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                year: 2023,
+                day: 1,
+                part: 2,
+                label: "Calibration values",
+                result: Ok("281".to_string()),
+                duration: Duration::from_nanos(1500),
+                memory: Some(AllocStats {
+                    peak_bytes: 2048,
+                    total_bytes: 4096,
+                    allocations: 3,
+                }),
+            },
+            Record {
+                year: 2023,
+                day: 4,
+                part: 1,
+                label: "Scratch card points",
+                result: Err(Error::at_line(3, "Missing \"|\", found x")),
+                duration: Duration::from_nanos(20),
+                memory: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn json_report() {
+        let msg = "should format records as a JSON array";
+        let expected = [
+            "[",
+            r#"  {"year":2023,"day":1,"part":2,"label":"Calibration values","answer":"281","duration_ns":1500,"peak_bytes":2048,"alloc_bytes":4096,"allocs":3,"status":"ok","error":null},"#,
+            r#"  {"year":2023,"day":4,"part":1,"label":"Scratch card points","answer":null,"duration_ns":20,"peak_bytes":null,"alloc_bytes":null,"allocs":null,"status":"error","error":"line 3: Missing \"|\", found x"}"#,
+            "]",
+        ]
+        .join("\n");
+        let actual = to_json(&records());
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn csv_report() {
+        let msg = "should format records as CSV";
+        let expected = [
+            CSV_HEADER,
+            "2023,1,2,Calibration values,281,1500,2048,4096,3,ok,",
+            r#"2023,4,1,Scratch card points,,20,,,,error,"line 3: Missing ""|"", found x""#,
+        ]
+        .join("\n");
+        let actual = to_csv(&records());
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn text_report() {
+        let msg = "should show heap usage next to the run time when counted";
+        let expected =
+            "Part 2 - Calibration values: 281 (1.5µs, peak 2.0 KiB, 4.0 KiB in 3 allocs)";
+        let actual = records()[0].to_string();
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn parse_format() {
+        let msg = "should parse an output format name";
+        let expected = Ok(Format::Json);
+        let actual = "JSON".parse();
+        assert_eq!(actual, expected, "{}", msg);
+
+        let actual = "xml".parse::<Format>().is_err();
+        assert!(actual, "{}", msg);
+    }
+}
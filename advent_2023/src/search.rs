@@ -0,0 +1,222 @@
+//! Shortest path search over [`Coord`](crate::Coord) grids and arbitrary hashable states
+//!
+//! Each search takes a `neighbors` closure listing the states reachable from a state, and an
+//! `is_goal` closure ending the search at the first goal state reached.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Shortest path found by a search
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Path<S> {
+    /// Every state along the path, from the start state to the goal
+    pub states: Vec<S>,
+    /// Total cost of the path
+    pub cost: usize,
+}
+
+/// Visited states, each with the index of the state it was reached from
+#[derive(Debug)]
+struct Visited<S> {
+    states: Vec<(S, Option<usize>)>,
+}
+
+impl<S: Clone> Visited<S> {
+    /// record a visited state, returning its index
+    fn push(&mut self, state: S, parent: Option<usize>) -> usize {
+        self.states.push((state, parent));
+        self.states.len() - 1
+    }
+
+    /// the path to the state at `idx`, from the start state
+    fn path_to(&self, mut idx: usize, cost: usize) -> Path<S> {
+        let mut states = Vec::new();
+        loop {
+            let (state, parent) = &self.states[idx];
+            states.push(state.clone());
+            match parent {
+                Some(parent) => idx = *parent,
+                None => break,
+            }
+        }
+        states.reverse();
+
+        Path { states, cost }
+    }
+}
+
+/// breadth first search for the path with the fewest steps from `start` to a goal state
+///
+/// The cost of the returned path is its step count
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut visited = Visited { states: Vec::new() };
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(visited.push(start, None), 0)]);
+
+    while let Some((idx, cost)) = queue.pop_front() {
+        let state = visited.states[idx].0.clone();
+        if is_goal(&state) {
+            return Some(visited.path_to(idx, cost));
+        }
+
+        for next in neighbors(&state) {
+            if seen.insert(next.clone()) {
+                queue.push_back((visited.push(next, Some(idx)), cost + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra search for the cheapest path from `start` to a goal state
+///
+/// `neighbors` lists each reachable state with the cost of the step to it
+pub fn dijkstra<S, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    astar(start, neighbors, |_| 0, is_goal)
+}
+
+/// A* search for the cheapest path from `start` to a goal state
+///
+/// `neighbors` lists each reachable state with the cost of the step to it. `heuristic` estimates
+/// the remaining cost to a goal, and must never overestimate it for the path to be the cheapest.
+pub fn astar<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut visited = Visited { states: Vec::new() };
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let estimate = heuristic(&start);
+    let mut queue = BinaryHeap::from([Reverse((estimate, 0, visited.push(start, None)))]);
+
+    while let Some(Reverse((_, cost, idx))) = queue.pop() {
+        let state = visited.states[idx].0.clone();
+        // skip stale entries superseded by a cheaper path
+        if costs.get(&state).is_some_and(|best| cost > *best) {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some(visited.path_to(idx, cost));
+        }
+
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_some_and(|best| next_cost >= *best) {
+                continue;
+            }
+
+            costs.insert(next.clone(), next_cost);
+            let estimate = next_cost + heuristic(&next);
+            queue.push(Reverse((
+                estimate,
+                next_cost,
+                visited.push(next, Some(idx)),
+            )));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{load_terrain, Coord, Grid};
+
+    fn maze() -> (Grid<char>, Coord, Coord) {
+        let grid: Grid<char> = load_terrain("input/search/maze-t.txt").unwrap();
+        let find = |target| {
+            grid.iter_coords()
+                .find(|(_, ch)| **ch == target)
+                .map(|(coord, _)| coord)
+                .unwrap()
+        };
+        let (start, end) = (find('S'), find('E'));
+
+        (grid, start, end)
+    }
+
+    #[test]
+    fn bfs_grid() {
+        let msg = "should find the path with the fewest steps through the maze";
+        let (grid, start, end) = maze();
+        let open = |coord: &Coord| {
+            grid.neighbors4(*coord)
+                .filter(|next| grid[*next] != '#')
+                .collect::<Vec<_>>()
+        };
+        let actual = bfs(start, open, |coord| *coord == end).unwrap();
+        assert_eq!(actual.cost, 12, "{}", msg);
+        assert_eq!(actual.states.len(), 13, "{}", msg);
+        assert_eq!(actual.states.first(), Some(&start), "{}", msg);
+        assert_eq!(actual.states.last(), Some(&end), "{}", msg);
+
+        let msg = "should return None when no goal is reachable";
+        let actual = bfs(start, open, |coord| *coord == Coord(9, 9));
+        assert_eq!(actual, None, "{}", msg);
+    }
+
+    #[test]
+    fn bfs_states() {
+        let msg = "should search arbitrary hashable states";
+        let expected = vec![1, 2, 4, 5, 10];
+        let actual = bfs(1, |n| [n + 1, n * 2], |n| *n == 10).unwrap().states;
+        assert_eq!(actual, expected, "{}", msg);
+    }
+
+    #[test]
+    fn weighted_grid() {
+        let grid: Grid<char> = load_terrain("input/search/weights-t.txt").unwrap();
+        let end = Coord::from((grid.height() - 1, grid.width() - 1));
+        let weighted = |coord: &Coord| {
+            grid.neighbors4(*coord)
+                .map(|next| (next, grid[next].to_digit(10).unwrap() as usize))
+                .collect::<Vec<_>>()
+        };
+
+        let msg = "should find the cheapest path";
+        let actual = dijkstra(Coord(0, 0), weighted, |coord| *coord == end).unwrap();
+        assert_eq!(actual.cost, 21, "{}", msg);
+
+        let msg = "should find the cheapest path guided by a heuristic";
+        let actual = astar(
+            Coord(0, 0),
+            weighted,
+            |coord| coord.manhattan(end),
+            |coord| *coord == end,
+        )
+        .unwrap();
+        assert_eq!(actual.cost, 21, "{}", msg);
+        let steps: usize = actual
+            .states
+            .iter()
+            .skip(1)
+            .map(|coord| grid[*coord].to_digit(10).unwrap() as usize)
+            .sum();
+        assert_eq!(steps, actual.cost, "{}", msg);
+    }
+}
@@ -0,0 +1,146 @@
+//! Isolated solver runs, so a panicking or runaway solver only fails its own part
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    memory::{self, AllocStats},
+    Error, Solver,
+};
+
+/// Default time limit for a single solver run
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Output of a solver run, with its run time and heap usage
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome<T> {
+    /// Solver output
+    pub result: Result<T, Error>,
+    /// Solver run time
+    pub duration: Duration,
+    /// Solver heap usage, when allocations are counted and the solver finished
+    pub memory: Option<AllocStats>,
+}
+
+impl<T> Outcome<T> {
+    /// the outcome of a solver which didn't finish
+    fn failed(err: Error, duration: Duration) -> Self {
+        Self {
+            result: Err(err),
+            duration,
+            memory: None,
+        }
+    }
+}
+
+/// run `solver` on `input` on its own thread and return its output, run time and heap usage
+///
+/// A panic is reported as a `PANIC: <message>` error. A solver still running after `timeout`
+/// is reported as a `TIMEOUT` error and left running in the background, since threads can't be
+/// cancelled. Without a `timeout` the solver may run indefinitely.
+pub fn run<T: Send + 'static>(
+    solver: Solver<T>,
+    input: Arc<str>,
+    timeout: Option<Duration>,
+) -> Outcome<T> {
+    let (sender, receiver) = mpsc::channel();
+    let start = Instant::now();
+    let spawned = thread::Builder::new()
+        .name("solver".to_string())
+        .spawn(move || {
+            let output = panic::catch_unwind(AssertUnwindSafe(|| {
+                let ((result, duration), memory) = memory::measure(|| {
+                    let start = Instant::now();
+                    let result = solver(&input);
+                    (result, start.elapsed())
+                });
+                Outcome {
+                    result,
+                    duration,
+                    memory,
+                }
+            }));
+            // the receiver is gone if the solver timed out
+            let _ = sender.send(output);
+        });
+    if let Err(err) = spawned {
+        let err = Error::new(format!("couldnt start solver thread: {}", err));
+        return Outcome::failed(err, start.elapsed());
+    }
+
+    let output = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).map_err(|err| match err {
+            mpsc::RecvTimeoutError::Timeout => Error::timeout(timeout),
+            mpsc::RecvTimeoutError::Disconnected => Error::panic("solver thread stopped"),
+        }),
+        None => receiver
+            .recv()
+            .map_err(|_| Error::panic("solver thread stopped")),
+    };
+
+    match output {
+        Ok(Ok(outcome)) => outcome,
+        Ok(Err(payload)) => {
+            Outcome::failed(Error::panic(panic_message(&*payload)), start.elapsed())
+        }
+        Err(err) => Outcome::failed(err, start.elapsed()),
+    }
+}
+
+/// the message of a panic payload, as passed to `panic!`
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ErrorKind;
+
+    fn length(input: &str) -> Result<usize, Error> {
+        Ok(input.len())
+    }
+
+    fn panics(input: &str) -> Result<usize, Error> {
+        panic!("bad input {}", input)
+    }
+
+    fn sleeps(_input: &str) -> Result<usize, Error> {
+        thread::sleep(Duration::from_secs(2));
+        Ok(0)
+    }
+
+    #[test]
+    fn isolated_runs() {
+        let input: Arc<str> = "abc".into();
+
+        let msg = "should return the solver output";
+        let actual = run(length, input.clone(), Some(Duration::from_secs(5)));
+        assert_eq!(actual.result, Ok(3), "{}", msg);
+        assert_eq!(actual.memory.is_some(), memory::ENABLED, "{}", msg);
+
+        let msg = "should report a panic with its message";
+        let actual = run(panics, input.clone(), None).result.unwrap_err();
+        assert_eq!(actual.reason, "PANIC: bad input abc", "{}", msg);
+        assert_eq!(actual.kind, ErrorKind::Panic, "{}", msg);
+
+        let msg = "should stop waiting for a solver after the time limit";
+        let start = Instant::now();
+        let actual = run(sleeps, input, Some(Duration::from_millis(50)));
+        assert!(actual.duration < Duration::from_secs(1), "{}", msg);
+        let actual = actual.result.unwrap_err();
+        assert_eq!(actual.reason, "TIMEOUT after 0.05s", "{}", msg);
+        assert_eq!(actual.kind, ErrorKind::Timeout, "{}", msg);
+        assert!(start.elapsed() < Duration::from_secs(1), "{}", msg);
+    }
+}
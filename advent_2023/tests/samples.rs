@@ -0,0 +1,39 @@
+//! Sample input tests, generated for each part answer in a sample's sidecar answers file
+//!
+//! Adding `input/<YEAR>/NN-t*.txt` with a matching `NN-t*.answers.toml` adds a test for each
+//! answer listed in it, eg `input/2023/04-t.answers.toml`:
+//!
+//! ```toml
+//! [day04]
+//! part1 = 13
+//! part2 = 30
+//! ```
+use advent_2023::{answers::Answers, read_file, registry};
+
+/// solve a sample input and compare the result to its sidecar answer
+fn check_sample(year: usize, day: usize, part: usize, sample: &str) {
+    let dir = format!("{}/input/{}", env!("CARGO_MANIFEST_DIR"), year);
+    let answers = Answers::load(&format!("{}/{}.answers.toml", dir, sample)).unwrap();
+    let expected = answers
+        .get(day, part)
+        .unwrap_or_else(|| panic!("{}.answers.toml has no day {:02} answers", sample, day));
+
+    let solution = registry::get(year, day)
+        .unwrap_or_else(|| panic!("{} day {:02} is not registered", year, day));
+    let (_, solver) = match part {
+        1 => solution.one(),
+        2 => solution.two(),
+        _ => panic!("{}.answers.toml lists unknown part {}", sample, part),
+    }
+    .unwrap_or_else(|| panic!("{} day {:02} has no part {}", year, day, part));
+
+    let input = read_file(&format!("{}/{}.txt", dir, sample)).unwrap();
+    let actual = solver(&input).unwrap();
+    let msg = format!(
+        "should solve {} day {:02} part {} for {}",
+        year, day, part, sample
+    );
+    assert_eq!(actual, expected, "{}", msg);
+}
+
+include!(concat!(env!("OUT_DIR"), "/sample_tests.rs"));